@@ -0,0 +1,153 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::collections::BTreeMap;
+use yaku_checker::{regular_shanten, tile_counts, Tile, ALL_TILES};
+
+/// xorshift, enough to shuffle a wall
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+fn random_hands(num: usize) -> Vec<Vec<Tile>> {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    let mut wall = ALL_TILES
+        .iter()
+        .flat_map(|&tile| [tile; 4])
+        .collect::<Vec<_>>();
+    (0..num)
+        .map(|_| {
+            for index in (1..wall.len()).rev() {
+                wall.swap(index, rng.next() as usize % (index + 1));
+            }
+            let mut hand = wall[..13].to_vec();
+            hand.sort();
+            hand
+        })
+        .collect()
+}
+
+/// the recursive search used before looking up suit entries
+fn old_shanten(tiles: &[Tile]) -> u8 {
+    let mut tile_left = BTreeMap::new();
+    for &tile in tiles {
+        *tile_left.entry(tile).or_default() += 1;
+    }
+    let mut min_shanten = 8;
+    old_search(&mut tile_left, 0, 0, false, &mut min_shanten);
+    min_shanten
+}
+
+fn old_search(
+    tile_left: &mut BTreeMap<Tile, u8>,
+    completed: u8,
+    incompleted: u8,
+    have_pair: bool,
+    min_shanten: &mut u8,
+) {
+    if completed + incompleted - have_pair as u8 > 4 {
+        return;
+    }
+
+    let kvs = tile_left
+        .iter()
+        .filter_map(|(&k, &v)| if v > 0 { Some((k, v)) } else { None })
+        .collect::<Vec<_>>();
+
+    let current_shanten = 8 - 2 * completed - incompleted;
+    *min_shanten = (*min_shanten).min(current_shanten);
+    if kvs.is_empty() {
+        return;
+    }
+
+    let tile_left_num = kvs.iter().map(|(_, v)| *v).sum::<u8>();
+    if tile_left_num / 3 * 2 + (tile_left_num % 3) / 2 < current_shanten - *min_shanten {
+        return;
+    }
+
+    let mut take = |tile_left: &mut BTreeMap<Tile, u8>,
+                    tiles: &[Tile],
+                    completed: u8,
+                    incompleted: u8,
+                    have_pair: bool| {
+        for tile in tiles {
+            *tile_left.get_mut(tile).unwrap() -= 1;
+        }
+        old_search(tile_left, completed, incompleted, have_pair, min_shanten);
+        for tile in tiles {
+            *tile_left.get_mut(tile).unwrap() += 1;
+        }
+    };
+
+    let first = kvs[0].0;
+    if kvs[0].1 >= 3 {
+        take(
+            tile_left,
+            &[first; 3],
+            completed + 1,
+            incompleted,
+            have_pair,
+        );
+    }
+    if kvs.len() >= 3 && first.next() == Some(kvs[1].0) && kvs[1].0.next() == Some(kvs[2].0) {
+        take(
+            tile_left,
+            &[first, kvs[1].0, kvs[2].0],
+            completed + 1,
+            incompleted,
+            have_pair,
+        );
+    }
+    if kvs[0].1 >= 2 {
+        take(tile_left, &[first; 2], completed, incompleted + 1, true);
+    }
+    for &(other, _) in kvs.iter().skip(1).take(2) {
+        if first.is_numbered() && first.is_related(other) {
+            take(
+                tile_left,
+                &[first, other],
+                completed,
+                incompleted + 1,
+                have_pair,
+            );
+        }
+    }
+    take(tile_left, &[first], completed, incompleted, have_pair);
+}
+
+fn shanten(c: &mut Criterion) {
+    let hands = random_hands(200);
+    // compute the suit entries of these hands before timing
+    let counts = hands
+        .iter()
+        .map(|hand| tile_counts(hand))
+        .collect::<Vec<_>>();
+    for (hand, counts) in hands.iter().zip(&counts) {
+        assert_eq!(old_shanten(hand) as i8, regular_shanten(counts, 0));
+    }
+
+    let mut group = c.benchmark_group("regular shanten of 200 random hands");
+    group.bench_function("recursive search", |b| {
+        b.iter(|| {
+            for hand in &hands {
+                black_box(old_shanten(black_box(hand)));
+            }
+        })
+    });
+    group.bench_function("suit entries", |b| {
+        b.iter(|| {
+            for hand in &hands {
+                black_box(regular_shanten(&tile_counts(black_box(hand)), 0));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, shanten);
+criterion_main!(benches);
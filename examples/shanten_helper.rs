@@ -1,16 +1,32 @@
-use std::io::stdin;
-use yaku_checker::ReadyTileSet;
+use std::{env, io::stdin};
+use yaku_checker::{tile_counts, Budget, ReadyTileSet, Rules};
 
+/// pass a preset name such as `tenhou` or `wrc` as the first argument
 fn main() {
+    let rules = env::args()
+        .nth(1)
+        .map_or_else(|| Ok(Rules::default()), |name| name.parse::<Rules>())
+        .unwrap();
     let mut input = String::new();
     stdin().read_line(&mut input).unwrap();
-    let mut tiles = input.trim().parse::<ReadyTileSet>().unwrap();
+    let mut tiles = input
+        .trim()
+        .parse::<ReadyTileSet>()
+        .unwrap()
+        .with_rules(rules);
     loop {
-        println!("{:?}", tiles.check());
+        println!("{:?}", tiles.search(Budget::default()));
+        // only our own tiles are known here
+        println!("{:?}", tiles.ukeire(&tile_counts(tiles.concealed())));
         input.clear();
         stdin().read_line(&mut input).unwrap();
         let full_tiles = tiles.draw(input.trim().parse().unwrap());
         println!("{:?}", full_tiles.yakus());
+        for option in
+            full_tiles.discard_options(&tile_counts(full_tiles.concealed()), Budget::default())
+        {
+            println!("{option}");
+        }
         input.clear();
         stdin().read_line(&mut input).unwrap();
         tiles = full_tiles.discard(input.trim().parse().unwrap()).unwrap();
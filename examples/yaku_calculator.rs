@@ -1,14 +1,31 @@
-use std::io::stdin;
-use yaku_checker::ReadyTileSet;
+use std::{env, io::stdin};
+use yaku_checker::{FullTileSet, ReadyTileSet, Rules};
 
+/// pass a preset name such as `tenhou` or `wrc` as the first argument
 fn main() {
+    let rules = env::args()
+        .nth(1)
+        .map_or_else(|| Ok(Rules::default()), |name| name.parse::<Rules>())
+        .unwrap();
     loop {
         let mut input = String::new();
         stdin().read_line(&mut input).unwrap();
-        let tiles = input.trim().parse::<ReadyTileSet>().unwrap();
+        // a whole hand in mpsz such as `123m406p789s1112z+2z`
+        if input.contains('+') {
+            let tiles = FullTileSet::from_mpsz(input.trim())
+                .unwrap()
+                .with_rules(rules);
+            println!("{:?}", tiles.result());
+            continue;
+        }
+        let tiles = input
+            .trim()
+            .parse::<ReadyTileSet>()
+            .unwrap()
+            .with_rules(rules);
         input.clear();
         stdin().read_line(&mut input).unwrap();
         let tiles = tiles.draw(input.trim().parse().unwrap());
-        println!("{:?}", tiles.yakus())
+        println!("{:?}", tiles.result())
     }
 }
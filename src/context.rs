@@ -0,0 +1,124 @@
+use crate::{
+    error::{Error, Result},
+    rules::Rules,
+    tile::{Tile, Wind},
+    T_INVALID,
+};
+
+/// 和了方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WinMethod {
+    /// 自摸
+    #[default]
+    Tsumo,
+    /// 荣和
+    Ron,
+}
+
+/// 立直宣言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Riichi {
+    /// 立直
+    Riichi,
+    /// 两立直
+    DoubleRiichi,
+}
+
+/// special situations of a win
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WinSituation {
+    /// 海底, tsumo on the last tile of the wall
+    pub haitei: bool,
+    /// 河底, ron on the last discard
+    pub houtei: bool,
+    /// 岭上, tsumo on the replacement tile of a kan
+    pub rinshan: bool,
+    /// 抢杠, ron on the tile added to a shouminkan
+    pub chankan: bool,
+    /// 天和 / 地和, tsumo on the first draw with no call before it,
+    /// or 人和, ron by a non-dealer before it
+    pub first_draw: bool,
+}
+
+/// everything about a win that can't be told from the tiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct WinContext {
+    pub(crate) win_method: WinMethod,
+    /// 场风
+    pub(crate) round_wind: Wind,
+    /// 自风
+    pub(crate) seat_wind: Wind,
+    pub(crate) riichi: Option<Riichi>,
+    /// 一发 is still live
+    pub(crate) ippatsu: bool,
+    pub(crate) situation: WinSituation,
+    /// padded with `T_INVALID`
+    pub(crate) dora_indicators: [Tile; 5],
+    /// padded with `T_INVALID`
+    pub(crate) ura_indicators: [Tile; 5],
+    /// 本场
+    pub(crate) honba: u8,
+    /// riichi sticks on the table
+    pub(crate) riichi_sticks: u8,
+    pub(crate) rules: Rules,
+}
+
+impl Default for WinContext {
+    /// east round, south seat, so not the dealer
+    fn default() -> Self {
+        WinContext {
+            win_method: WinMethod::default(),
+            round_wind: Wind::Ton,
+            seat_wind: Wind::Nan,
+            riichi: None,
+            ippatsu: false,
+            situation: WinSituation::default(),
+            dora_indicators: [T_INVALID; 5],
+            ura_indicators: [T_INVALID; 5],
+            honba: 0,
+            riichi_sticks: 0,
+            rules: Rules::default(),
+        }
+    }
+}
+
+impl WinContext {
+    pub(crate) fn with_winds(self, round_wind: Wind, seat_wind: Wind) -> Self {
+        WinContext {
+            round_wind,
+            seat_wind,
+            ..self
+        }
+    }
+
+    /// at most 5 indicators each, including those revealed by kan
+    pub(crate) fn with_dora(self, indicators: &[Tile], ura_indicators: &[Tile]) -> Result<Self> {
+        if indicators.len() > 5 || ura_indicators.len() > 5 {
+            return Err(Error::TooManyDoraIndicators(
+                indicators.len().max(ura_indicators.len()),
+            ));
+        }
+        let mut context = self;
+        context.dora_indicators = [T_INVALID; 5];
+        context.dora_indicators[..indicators.len()].copy_from_slice(indicators);
+        context.ura_indicators = [T_INVALID; 5];
+        context.ura_indicators[..ura_indicators.len()].copy_from_slice(ura_indicators);
+        Ok(context)
+    }
+
+    pub(crate) fn with_rules(self, rules: Rules) -> Self {
+        WinContext { rules, ..self }
+    }
+
+    pub(crate) fn is_dealer(&self) -> bool {
+        self.seat_wind == Wind::Ton
+    }
+
+    pub(crate) fn with_riichi(self, riichi: Riichi, ippatsu: bool) -> Self {
+        WinContext {
+            riichi: Some(riichi),
+            ippatsu,
+            ..self
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{tile::Tile, tile_block::TileBlock, wait::Wait};
+use std::{collections::BTreeMap, fmt::Display};
+
+/// one way to split a hand into blocks
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Decomposition {
+    blocks: Vec<TileBlock>,
+    /// index of the block completed by the winning tile, and the tile itself
+    winning: Option<(usize, Tile)>,
+}
+
+impl Decomposition {
+    pub(crate) fn new(blocks: Vec<TileBlock>, winning: Option<(usize, Tile)>) -> Self {
+        Decomposition { blocks, winning }
+    }
+
+    /// sorted, melds included
+    pub fn blocks(&self) -> &[TileBlock] {
+        &self.blocks
+    }
+
+    /// `None` if no winning tile is given
+    pub fn win_block(&self) -> Option<&TileBlock> {
+        self.winning.map(|(index, _)| &self.blocks[index])
+    }
+
+    pub fn win_tile(&self) -> Option<Tile> {
+        self.winning.map(|(_, tile)| tile)
+    }
+
+    pub fn wait(&self) -> Option<Wait> {
+        self.winning
+            .map(|(index, tile)| Wait::of(&self.blocks[index], tile))
+    }
+}
+
+impl Display for Decomposition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = self
+            .blocks
+            .iter()
+            .map(|block| block.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "{text}")
+    }
+}
+
+/// every decomposition of a closed hand with 3n + 2 tiles, no winning tile marked
+pub fn decompose(tiles: &[Tile]) -> impl Iterator<Item = Decomposition> {
+    let mut tiles = tiles.to_vec();
+    tiles.sort();
+    let patterns = if tiles.len() % 3 == 2 && tiles.len() <= 14 {
+        find_blocks(&tiles, &[])
+    } else {
+        vec![]
+    };
+    patterns
+        .into_iter()
+        .map(|blocks| Decomposition::new(blocks, None))
+}
+
+/// all possible block lists of sorted concealed tiles and melds
+pub(crate) fn find_blocks(tiles: &[Tile], meld_blocks: &[TileBlock]) -> Vec<Vec<TileBlock>> {
+    let mut patterns = vec![];
+
+    // kokushi and chiitoi can't have any meld
+    if tiles.len() == 14
+        && tiles
+            .iter()
+            .all(|tile| tile.is_terminal() || tile.is_honor())
+        && tiles.windows(2).filter(|pair| pair[0] == pair[1]).count() == 1
+    {
+        patterns.push(
+            tiles
+                .iter()
+                .map(|&tile| TileBlock::new_unknown(&[tile]).unwrap())
+                .collect(),
+        );
+    }
+
+    // check chiitoi
+    if tiles.len() == 14
+        && tiles.chunks(2).all(|pair| pair[0] == pair[1])
+        && tiles.windows(3).all(|tri| tri[0] != tri[2])
+    {
+        patterns.push(
+            tiles
+                .chunks(2)
+                .map(|tiles| TileBlock::new_unknown(tiles).unwrap())
+                .collect(),
+        );
+    }
+
+    // fast test for common pattern
+    let mut index = 0;
+    let mut last_valid = false;
+    while index < tiles.len() - 1 {
+        if tiles[index].is_related(tiles[index + 1]) {
+            last_valid = true;
+        } else if last_valid && index != tiles.len() - 2 {
+            last_valid = false;
+        } else {
+            return patterns;
+        }
+        index += 1;
+    }
+
+    // check common, counted as normal tiles and red fives put back afterwards
+    let mut tile_left = BTreeMap::new();
+    for &tile in tiles {
+        *tile_left.entry(tile.to_normal()).or_default() += 1;
+    }
+    let reds = tiles
+        .iter()
+        .filter(|tile| tile.is_red())
+        .copied()
+        .collect::<Vec<_>>();
+    let group_left = (tiles.len() / 3) as u8;
+    for mut pattern in find_common_patterns(&mut tile_left, group_left, 1) {
+        pattern.sort();
+        for &red in &reds {
+            pattern.iter_mut().any(|block| block.paint_red(red));
+        }
+        pattern.extend_from_slice(meld_blocks);
+        pattern.sort();
+        patterns.push(pattern);
+    }
+    patterns.sort();
+    patterns.dedup();
+    patterns
+}
+
+fn find_common_patterns(
+    tile_left: &mut BTreeMap<Tile, u8>,
+    group_left: u8,
+    pair_left: u8,
+) -> Vec<Vec<TileBlock>> {
+    if group_left == 0 && pair_left == 0 {
+        return vec![vec![]];
+    }
+    let mut ret = vec![];
+
+    let ks = tile_left
+        .iter()
+        .filter_map(|(&k, &v)| if v > 0 { Some(k) } else { None })
+        .take(3)
+        .collect::<Vec<_>>();
+
+    // continue with a pair
+    if pair_left > 0 && *tile_left.get(&ks[0]).unwrap() >= 2 {
+        let current = TileBlock::new_pair([ks[0]; 2]).unwrap();
+        *tile_left.get_mut(&ks[0]).unwrap() -= 2;
+        find_common_patterns(tile_left, group_left, pair_left - 1)
+            .into_iter()
+            .map(|mut v| {
+                v.push(current);
+                v
+            })
+            .for_each(|v| ret.push(v));
+        *tile_left.get_mut(&ks[0]).unwrap() += 2;
+    }
+
+    // continue with a triplet
+    if group_left > 0 && *tile_left.get(&ks[0]).unwrap() >= 3 {
+        let current = TileBlock::new_triplet([ks[0]; 3]).unwrap();
+        *tile_left.get_mut(&ks[0]).unwrap() -= 3;
+        find_common_patterns(tile_left, group_left - 1, pair_left)
+            .into_iter()
+            .map(|mut v| {
+                v.push(current);
+                v
+            })
+            .for_each(|v| ret.push(v));
+        *tile_left.get_mut(&ks[0]).unwrap() += 3;
+    }
+
+    // continue with a sequence
+    if group_left > 0 && ks.len() >= 3 {
+        if let Ok(current) = TileBlock::new_sequence([ks[0], ks[1], ks[2]]) {
+            *tile_left.get_mut(&current.tiles()[0]).unwrap() -= 1;
+            *tile_left.get_mut(&current.tiles()[1]).unwrap() -= 1;
+            *tile_left.get_mut(&current.tiles()[2]).unwrap() -= 1;
+            find_common_patterns(tile_left, group_left - 1, pair_left)
+                .into_iter()
+                .map(|mut v| {
+                    v.push(current);
+                    v
+                })
+                .for_each(|v| ret.push(v));
+            *tile_left.get_mut(&current.tiles()[0]).unwrap() += 1;
+            *tile_left.get_mut(&current.tiles()[1]).unwrap() += 1;
+            *tile_left.get_mut(&current.tiles()[2]).unwrap() += 1;
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn decompose_tiles() {
+        let tiles = [
+            T_1P, T_1P, T_1P, T_2P, T_2P, T_2P, T_3P, T_3P, T_3P, T_5S, T_5S,
+        ];
+        let decompositions = decompose(&tiles).collect::<Vec<_>>();
+        assert_eq!(decompositions.len(), 2);
+        assert_eq!(
+            decompositions[0].to_string(),
+            "[1p 1p 1p] [2p 2p 2p] [3p 3p 3p] [5s 5s]"
+        );
+        assert_eq!(decompositions[1].blocks()[0].kind(), BlockKind::Sequence);
+        assert!(decompositions
+            .iter()
+            .all(|decomposition| decomposition.win_block().is_none()));
+
+        assert_eq!(decompose(&[T_1P, T_2P, T_3P]).count(), 0);
+        assert_eq!(decompose(&[T_1P, T_2P, T_4P, T_5S, T_5S]).count(), 0);
+        assert_eq!(decompose(&[T_CHUN, T_CHUN]).count(), 1);
+
+        // red fives keep their identity
+        let tiles = [
+            T_0P, T_4P, T_6P, T_5M, T_0M, T_5M, T_5S, T_5S, T_5S, T_TON, T_TON,
+        ];
+        let decompositions = decompose(&tiles).collect::<Vec<_>>();
+        assert_eq!(decompositions.len(), 1);
+        assert_eq!(
+            decompositions[0].to_string(),
+            "[0m 5m 5m] [4p 0p 6p] [5s 5s 5s] [ton ton]"
+        );
+    }
+}
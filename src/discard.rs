@@ -0,0 +1,69 @@
+use crate::{tile::Tile, ukeire::Ukeire, yaku::Han};
+use std::fmt::Display;
+
+/// one discard of a 14 tile hand and why it ranks where it does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscardOption {
+    pub discard: Tile,
+    /// shanten after the discard, 0 for tenpai
+    pub shanten: i8,
+    /// tiles that lower the shanten after the discard
+    pub ukeire: Ukeire,
+    /// best ron points among the waits still live, only for tenpai,
+    /// 0 if no wait has a yaku
+    pub tenpai_value: Option<u32>,
+    /// most han of yaku among the wins `ReadyTileSet::search` reaches after the discard,
+    /// dora not counted, only before tenpai, `None` if no win with a yaku was reached
+    pub reachable_han: Option<Han>,
+    /// the search ran out of budget, so `reachable_han` may be too low
+    pub exhausted: bool,
+}
+
+impl DiscardOption {
+    /// lower shanten first, then more unseen effective tiles, then higher tenpai value,
+    /// then more reachable han
+    pub(crate) fn rank(options: &mut [DiscardOption]) {
+        options.sort_by(|a, b| {
+            a.shanten
+                .cmp(&b.shanten)
+                .then(b.ukeire.total.cmp(&a.ukeire.total))
+                .then(b.tenpai_value.cmp(&a.tenpai_value))
+                .then(b.reachable_han.cmp(&a.reachable_han))
+        });
+    }
+}
+
+impl Display for DiscardOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "discard {}: ", self.discard)?;
+        if self.shanten == 0 {
+            write!(f, "tenpai")?;
+        } else {
+            write!(f, "{} shanten", self.shanten)?;
+        }
+        let tiles = self
+            .ukeire
+            .tiles
+            .iter()
+            .map(|(tile, unseen)| format!("{tile}x{unseen}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(
+            f,
+            ", {} tiles of {} kinds ({tiles})",
+            self.ukeire.total,
+            self.ukeire.kinds()
+        )?;
+        match self.tenpai_value {
+            Some(0) => write!(f, ", no yaku")?,
+            Some(points) => write!(f, ", up to {points} points")?,
+            None => {}
+        }
+        let more = if self.exhausted { " or more" } else { "" };
+        match self.reachable_han {
+            Some(han) if han.is_yakuman() => write!(f, ", reaching yakuman{more}"),
+            Some(han) => write!(f, ", reaching {} han{more}", han.value()),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{tile::Tile, T_INVALID};
+
+/// 宝牌, counted apart from yaku so it never makes a hand valid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Dora {
+    /// 宝牌, including 杠宝牌
+    pub dora: u8,
+    /// 里宝牌, only after riichi
+    pub ura_dora: u8,
+    /// 赤宝牌
+    pub aka_dora: u8,
+}
+
+impl Dora {
+    pub fn total(&self) -> u8 {
+        self.dora + self.ura_dora + self.aka_dora
+    }
+}
+
+/// how many tiles are dora of these indicators, one indicator may hit a tile more than once
+pub(crate) fn count_dora(tiles: &[Tile], indicators: &[Tile]) -> u8 {
+    indicators
+        .iter()
+        .filter(|&&indicator| indicator != T_INVALID)
+        .map(|indicator| {
+            let dora = indicator.indicated_dora();
+            tiles.iter().filter(|&&tile| tile == dora).count() as u8
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn count() {
+        let tiles = [T_1M, T_1M, T_2M, T_HAKU, T_TON];
+        assert_eq!(count_dora(&tiles, &[T_9M]), 2);
+        assert_eq!(count_dora(&tiles, &[T_9M, T_9M]), 4);
+        assert_eq!(count_dora(&tiles, &[T_CHUN, T_PEI, T_INVALID]), 2);
+        assert_eq!(count_dora(&tiles, &[T_2M]), 0);
+        assert_eq!(count_dora(&tiles, &[]), 0);
+        assert_eq!(count_dora(&[T_0M, T_5M], &[T_4M]), 2);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{meld::Meld, rules::Rules, tile::Tile};
+use std::{fmt::Display, ops::Range};
+
+pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// every way the library can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// text that is not a tile or a hand
+    Parse(ParseError),
+    /// not one of `Rules::PRESETS`
+    UnknownPreset(String),
+    /// tiles that don't form the block asked for, such as a chi of 1p 2p 4p
+    InvalidBlock(Vec<Tile>),
+    /// only a pon can be extended to a kan
+    NotAPon(Meld),
+    TooManyMelds(usize),
+    /// concealed tiles and melds of a ready hand, a kan counted as 3 tiles
+    WrongTileCount(usize),
+    /// discarding a tile that is not among the concealed tiles
+    NoSuchTile(Tile),
+    /// riichi with an open hand
+    OpenRiichi,
+    /// round or seat wind that is not a wind
+    NotAWind(Tile),
+    NotADragon(Tile),
+    /// a second red five of the same suit
+    ExtraRedFive(Tile),
+    /// a fifth copy of a tile, red fives count as fives
+    TooManyCopies(Tile),
+    /// more than 5 dora or ura dora indicators
+    TooManyDoraIndicators(usize),
+    InvalidSituation(SituationConflict),
+}
+
+/// parts of `WinSituation` that can't go together with the hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SituationConflict {
+    /// haitei or rinshan with ron
+    RonOnDraw,
+    /// houtei or chankan with tsumo
+    TsumoOnDiscard,
+    /// dealer ron before the first draw
+    DealerFirstDrawRon,
+    /// the replacement tile of a kan is not the last tile
+    HaiteiRinshan,
+    /// rinshan without any kan meld
+    RinshanWithoutKan,
+    /// the tile added to a kan is not a discard
+    HouteiChankan,
+    /// first draw after a call, a riichi or a kan
+    FirstDrawInterrupted,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{error}"),
+            Error::UnknownPreset(name) => {
+                write!(f, "\"{name}\" is not one of {}", Rules::PRESETS.join(", "))
+            }
+            Error::InvalidBlock(tiles) => write!(f, "{tiles:?} is not a block"),
+            Error::NotAPon(meld) => write!(f, "{meld} is not a pon"),
+            Error::TooManyMelds(num) => write!(f, "too many melds: {num}"),
+            Error::WrongTileCount(num) => write!(f, "wrong number of tiles: {num}"),
+            Error::NoSuchTile(tile) => write!(f, "no {tile} to discard"),
+            Error::OpenRiichi => write!(f, "can't riichi with an open hand"),
+            Error::NotAWind(tile) => write!(f, "{tile} is not a wind"),
+            Error::NotADragon(tile) => write!(f, "{tile} is not a dragon"),
+            Error::ExtraRedFive(tile) => write!(f, "only one {tile} is allowed"),
+            Error::TooManyCopies(tile) => write!(f, "more than 4 copies of {tile}"),
+            Error::TooManyDoraIndicators(num) => write!(f, "too many dora indicators: {num}"),
+            Error::InvalidSituation(conflict) => write!(f, "{conflict}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<SituationConflict> for Error {
+    fn from(conflict: SituationConflict) -> Self {
+        Error::InvalidSituation(conflict)
+    }
+}
+
+impl Display for SituationConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            SituationConflict::RonOnDraw => "haitei and rinshan must be tsumo",
+            SituationConflict::TsumoOnDiscard => "houtei and chankan must be ron",
+            SituationConflict::DealerFirstDrawRon => "dealer can't ron before the first draw",
+            SituationConflict::HaiteiRinshan => "replacement tile of a kan is not the last tile",
+            SituationConflict::RinshanWithoutKan => "rinshan needs a kan",
+            SituationConflict::HouteiChankan => "tile added to a kan is not a discard",
+            SituationConflict::FirstDrawInterrupted => "first draw can't follow any call or riichi",
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// why a tile or a hand could not be parsed, and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// byte range of the bad token in the input
+    pub span: Range<usize>,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// text that is not a tile in any notation
+    UnknownTile(String),
+    /// a repeat count of 0 or more than 4, or a fifth copy of the tile
+    CountOutOfRange { tile: Tile, count: usize },
+    /// 13 tiles for a ready hand, 14 for a full hand, 1 drawn tile, one more for each kan
+    WrongHandSize { expected: usize, found: usize },
+    /// a bracketed group that is not 4 copies of a tile
+    NotAKan(String),
+    /// a second red five of the same suit
+    ExtraRedFive(Tile),
+    /// tiles that parse but don't make a hand
+    InvalidHand(Box<Error>),
+}
+
+impl ParseError {
+    pub(crate) fn new(span: Range<usize>, kind: ParseErrorKind) -> Self {
+        ParseError { span, kind }
+    }
+
+    /// the whole of `s` is not a tile
+    pub(crate) fn unknown_tile(s: &str) -> Self {
+        ParseError::new(0..s.len(), ParseErrorKind::UnknownTile(s.to_string()))
+    }
+
+    /// the whole of `s` parses but makes no hand
+    pub(crate) fn invalid_hand(s: &str, error: Error) -> Self {
+        ParseError::new(0..s.len(), ParseErrorKind::InvalidHand(Box::new(error)))
+    }
+
+    /// move the span by `offset` bytes, for a token inside a larger input
+    pub(crate) fn offset(self, offset: usize) -> Self {
+        ParseError {
+            span: self.span.start + offset..self.span.end + offset,
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ParseErrorKind::UnknownTile(token) => write!(f, "\"{token}\" is not a tile")?,
+            ParseErrorKind::CountOutOfRange { tile, count } => {
+                write!(f, "{count} copies of {tile}, expected 1 to 4")?
+            }
+            ParseErrorKind::WrongHandSize { expected, found } => {
+                write!(f, "{found} tiles, expected {expected}")?
+            }
+            ParseErrorKind::NotAKan(group) => write!(f, "\"{group}\" is not a kan")?,
+            ParseErrorKind::ExtraRedFive(tile) => write!(f, "only one {tile} is allowed")?,
+            ParseErrorKind::InvalidHand(error) => write!(f, "{error}")?,
+        }
+        write!(f, " at {}..{}", self.span.start, self.span.end)
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,105 @@
+use crate::{tile::Tile, wait::Wait};
+
+/// where fu comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuItem {
+    /// 副底
+    Base,
+    /// 七对子, fixed 25 fu
+    Chiitoitsu,
+    /// 门前清荣和
+    MenzenRon,
+    /// 自摸
+    Tsumo,
+    /// 刻子 or 杠子
+    Triplet {
+        tile: Tile,
+        is_quad: bool,
+        is_concealed: bool,
+    },
+    /// 役牌雀头
+    YakuhaiPair(Tile),
+    /// 嵌张, 边张 or 单骑
+    Wait(Wait),
+    /// 副露平和型, 20 fu is raised to 30 fu
+    OpenPinfu,
+}
+
+/// 符, with an itemized breakdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fu {
+    pub items: Vec<(FuItem, u8)>,
+}
+
+impl Fu {
+    /// rounded up to the next 10, except chiitoitsu
+    pub fn total(&self) -> u8 {
+        let sum = self.items.iter().map(|(_, fu)| fu).sum::<u8>();
+        if self
+            .items
+            .iter()
+            .any(|(item, _)| *item == FuItem::Chiitoitsu)
+        {
+            sum
+        } else {
+            sum.div_ceil(10) * 10
+        }
+    }
+}
+
+impl FuItem {
+    pub(crate) fn triplet(tile: Tile, is_quad: bool, is_concealed: bool) -> (Self, u8) {
+        let mut fu = 2;
+        if tile.is_terminal() || tile.is_honor() {
+            fu *= 2;
+        }
+        if is_concealed {
+            fu *= 2;
+        }
+        if is_quad {
+            fu *= 4;
+        }
+        (
+            FuItem::Triplet {
+                tile,
+                is_quad,
+                is_concealed,
+            },
+            fu,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn triplet() {
+        assert_eq!(FuItem::triplet(T_2M, false, false).1, 2);
+        assert_eq!(FuItem::triplet(T_9M, false, false).1, 4);
+        assert_eq!(FuItem::triplet(T_2M, false, true).1, 4);
+        assert_eq!(FuItem::triplet(T_TON, false, true).1, 8);
+        assert_eq!(FuItem::triplet(T_5S, true, false).1, 8);
+        assert_eq!(FuItem::triplet(T_CHUN, true, false).1, 16);
+        assert_eq!(FuItem::triplet(T_5S, true, true).1, 16);
+        assert_eq!(FuItem::triplet(T_1P, true, true).1, 32);
+    }
+
+    #[test]
+    fn total() {
+        let fu = Fu {
+            items: vec![(FuItem::Base, 20), (FuItem::Tsumo, 2)],
+        };
+        assert_eq!(fu.total(), 30);
+        let fu = Fu {
+            items: vec![(FuItem::Base, 20), (FuItem::MenzenRon, 10)],
+        };
+        assert_eq!(fu.total(), 30);
+        let fu = Fu {
+            items: vec![(FuItem::Chiitoitsu, 25)],
+        };
+        assert_eq!(fu.total(), 25);
+    }
+}
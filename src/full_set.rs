@@ -1,172 +1,477 @@
-use std::{collections::BTreeMap, vec};
-
 use super::ready_set::ReadyTileSet;
 use crate::{
-    tile::Tile,
+    context::{Riichi, WinContext, WinMethod, WinSituation},
+    decomposition::{find_blocks, Decomposition},
+    discard::DiscardOption,
+    dora::{count_dora, Dora},
+    error::{Error, ParseError, ParseErrorKind, Result, SituationConflict},
+    fu::Fu,
+    meld::Meld,
+    notation::{check_copies, format_ankan, format_mpsz, parse_mpsz},
+    rules::{RenhouRule, Rules},
+    score::{Limit, Points},
+    search::{Budget, Search},
+    tile::{Tile, Wind},
     tile_block::TileBlock,
     tile_pattern::TilePattern,
+    wait::Wait,
+    win::{Win, WinResult},
     yaku::{Han, Yaku},
+    T_INVALID,
 };
-use anyhow::{anyhow, Result};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FullTileSet {
+    /// concealed tiles, sorted and padded with `T_INVALID`
     pub(crate) tiles: [Tile; 14],
+    pub(crate) melds: [Option<Meld>; 4],
     pub(crate) last_draw: Tile,
+    pub(crate) context: WinContext,
 }
 
 impl FullTileSet {
-    pub fn yakus(&self) -> Option<Vec<Yaku>> {
-        let patterns = self.patterns();
-        let mut possible_yakus = patterns
+    /// 14 concealed tiles in mpsz, the drawn tile follows a `+` as in
+    /// `123m406p789s112z+2z`, or is the last tile written without it,
+    /// each ankan such as `[1111z]` takes the place of 3 tiles before the `+`
+    pub fn from_mpsz(s: &str) -> Result<Self, ParseError> {
+        let (hand, draw) = match s.split_once('+') {
+            Some((hand, draw)) => {
+                let offset = hand.len() + 1;
+                let draw = parse_mpsz(draw).map_err(|error| error.offset(offset))?;
+                if draw.len() != 1 || !draw.kans.is_empty() {
+                    return Err(ParseError::new(
+                        offset..s.len(),
+                        ParseErrorKind::WrongHandSize {
+                            expected: 1,
+                            found: draw.len(),
+                        },
+                    ));
+                }
+                let (tile, span) = draw.tiles[0].clone();
+                (hand, vec![(tile, span.start + offset..span.end + offset)])
+            }
+            None => (s, vec![]),
+        };
+        let mut tokens = parse_mpsz(hand)?;
+        tokens.tiles.extend(draw);
+        check_copies(&tokens.all())?;
+        if tokens.tiles.len() + 3 * tokens.kans.len() != 14 {
+            return Err(ParseError::new(
+                0..s.len(),
+                ParseErrorKind::WrongHandSize {
+                    expected: 14 + tokens.kans.len(),
+                    found: tokens.len(),
+                },
+            ));
+        }
+        let (draw, _) = tokens.tiles.pop().unwrap();
+        Ok(ReadyTileSet::from_tokens(s, &tokens)?.draw(draw))
+    }
+
+    /// concealed tiles in mpsz with ankan in brackets and the drawn tile after a `+`,
+    /// other melds are not written
+    pub fn to_mpsz(&self) -> String {
+        let mut hand = self.concealed().to_vec();
+        if let Some(index) = hand
             .iter()
-            .map(|pattern| pattern.yakus())
-            .collect::<Vec<_>>();
-        if !patterns.is_empty() {
-            possible_yakus.push(vec![]);
+            .position(|&tile| tile == self.last_draw && tile.is_red() == self.last_draw.is_red())
+        {
+            hand.remove(index);
         }
-        possible_yakus.sort_by_key(|yakus| yakus.iter().map(|&yaku| yaku.into()).sum::<Han>());
-        possible_yakus.last().cloned()
+        format!(
+            "{}{}+{}",
+            format_mpsz(&hand),
+            format_ankan(self.melds()),
+            self.last_draw.to_mpsz()
+        )
     }
 
-    pub fn discard(self, tile: Tile) -> Result<ReadyTileSet> {
-        let mut tiles = self.tiles;
-        if let Some(index) = tiles.iter().position(|&t| t == tile) {
-            tiles[index..].rotate_left(1);
-            Ok(ReadyTileSet { tiles })
-        } else {
-            Err(anyhow!("no such tile"))
+    /// closed hand only, `ippatsu` means 一发 is still live
+    pub fn with_riichi(self, riichi: Riichi, ippatsu: bool) -> Result<Self> {
+        if !self.is_closed() {
+            return Err(Error::OpenRiichi);
         }
+        let full_set = FullTileSet {
+            context: self.context.with_riichi(riichi, ippatsu),
+            ..self
+        };
+        full_set.check_situation()?;
+        Ok(full_set)
     }
 
-    /// all possible patterns
-    fn patterns(&self) -> Vec<TilePattern> {
-        let mut patterns = vec![];
-        // check kokushi
-        if self
-            .tiles
-            .iter()
-            .all(|tile| tile.is_terminal() || tile.is_honor())
-            && self
-                .tiles
-                .windows(2)
-                .filter(|pair| pair[0] == pair[1])
-                .count()
-                == 1
-        {
-            patterns.push(TilePattern::new(
-                self.tiles
-                    .iter()
-                    .map(|&tile| TileBlock::new_unknown(&[tile]).unwrap())
-                    .collect(),
-                self.last_draw,
-            ));
+    pub fn with_situation(self, situation: WinSituation) -> Result<Self> {
+        let full_set = FullTileSet {
+            context: WinContext {
+                situation,
+                ..self.context
+            },
+            ..self
+        };
+        full_set.check_situation()?;
+        Ok(full_set)
+    }
+
+    pub fn with_rules(self, rules: Rules) -> Self {
+        FullTileSet {
+            context: self.context.with_rules(rules),
+            ..self
         }
+    }
 
-        // check chiitoi
-        if self.tiles.chunks(2).all(|pair| pair[0] == pair[1])
-            && self.tiles.windows(3).all(|tri| tri[0] != tri[2])
-        {
-            let pattern = self
-                .tiles
-                .chunks(2)
-                .map(|tiles| TileBlock::new_unknown(tiles).unwrap())
-                .collect();
-            patterns.push(TilePattern::new(pattern, self.last_draw));
-        }
-
-        // fast test for common pattern
-        let mut index = 0;
-        let mut last_valid = false;
-        while index < 13 {
-            if self.tiles[index].is_related(self.tiles[index + 1]) {
-                last_valid = true;
-            } else if last_valid && index != 12 {
-                last_valid = false;
+    /// east round and south seat if not set, the dealer sits east
+    pub fn with_winds(self, round_wind: Wind, seat_wind: Wind) -> Self {
+        FullTileSet {
+            context: self.context.with_winds(round_wind, seat_wind),
+            ..self
+        }
+    }
+
+    /// ura dora indicators only count after riichi
+    pub fn with_dora(self, indicators: &[Tile], ura_indicators: &[Tile]) -> Result<Self> {
+        Ok(FullTileSet {
+            context: self.context.with_dora(indicators, ura_indicators)?,
+            ..self
+        })
+    }
+
+    pub fn dora(&self) -> Dora {
+        let tiles = self.tiles();
+        Dora {
+            dora: count_dora(&tiles, &self.context.dora_indicators),
+            ura_dora: if self.context.riichi.is_some() && self.context.rules.ura_dora {
+                count_dora(&tiles, &self.context.ura_indicators)
             } else {
-                return patterns;
-            }
-            index += 1;
+                0
+            },
+            aka_dora: if self.context.rules.aka_dora {
+                tiles.iter().filter(|tile| tile.is_red()).count() as u8
+            } else {
+                0
+            },
+        }
+    }
+
+    /// 本场 and riichi sticks on the table
+    pub fn with_sticks(self, honba: u8, riichi_sticks: u8) -> Self {
+        FullTileSet {
+            context: WinContext {
+                honba,
+                riichi_sticks,
+                ..self.context
+            },
+            ..self
         }
+    }
+
+    /// `None` if the hand is not completed or has no yaku, dora included
+    pub fn points(&self) -> Option<Points> {
+        let (pattern, yakus) = self.best_pattern()?;
+        self.score(&pattern, &yakus)
+    }
 
-        // check common
-        let mut tile_left = BTreeMap::new();
-        for &tile in &self.tiles {
-            *tile_left.entry(tile).or_default() += 1;
+    /// dora only count when there is any yaku
+    fn score(&self, pattern: &TilePattern, yakus: &[Yaku]) -> Option<Points> {
+        if yakus.is_empty() {
+            return None;
         }
-        for mut pattern in Self::find_common_patterns(&mut tile_left, 4, 1) {
-            pattern.sort();
-            patterns.push(TilePattern::new(pattern, self.last_draw));
+        if let Some(limit) = self.limit(yakus) {
+            return Some(Points::limited(
+                limit,
+                self.context.is_dealer(),
+                self.win_method(),
+                self.context.honba,
+                self.context.riichi_sticks,
+            ));
         }
+        Some(Points::new(
+            self.total_han(yakus),
+            pattern.fu().total(),
+            self.context.is_dealer(),
+            self.win_method(),
+            self.context.honba,
+            self.context.riichi_sticks,
+            &self.context.rules,
+        ))
+    }
 
-        patterns.sort();
-        patterns.dedup();
-        patterns
+    /// han of yaku only
+    fn han(&self, yakus: &[Yaku]) -> Han {
+        yakus
+            .iter()
+            .map(|yaku| yaku.han(self.is_closed(), &self.context.rules))
+            .sum()
     }
 
-    fn find_common_patterns(
-        tile_left: &mut BTreeMap<Tile, u8>,
-        group_left: u8,
-        pair_left: u8,
-    ) -> Vec<Vec<TileBlock>> {
-        if group_left == 0 && pair_left == 0 {
-            return vec![vec![]];
+    /// han of yaku and dora, dora don't count with a limit yaku
+    fn total_han(&self, yakus: &[Yaku]) -> Han {
+        if self.limit(yakus).is_some() {
+            self.han(yakus)
+        } else {
+            self.han(yakus) + Han::new(self.dora().total())
         }
-        let mut ret = vec![];
+    }
+
+    fn limit(&self, yakus: &[Yaku]) -> Option<Limit> {
+        yakus
+            .iter()
+            .find_map(|yaku| yaku.limit(&self.context.rules))
+    }
+
+    /// `None` if the hand is not completed
+    pub fn result(&self) -> Option<WinResult> {
+        let (pattern, yakus) = self.best_pattern()?;
+        let decomposition = pattern.decomposition();
+        let Some(points) = self.score(&pattern, &yakus) else {
+            return Some(WinResult::NoYaku {
+                decomposition,
+                wait: pattern.wait(),
+            });
+        };
+        let dora = self.dora();
+        Some(WinResult::Win(Win {
+            decomposition,
+            wait: pattern.wait(),
+            yakus: yakus
+                .iter()
+                .map(|&yaku| (yaku, yaku.han(self.is_closed(), &self.context.rules)))
+                .collect(),
+            dora,
+            han: self.total_han(&yakus),
+            fu: pattern.fu(),
+            points,
+        }))
+    }
+
+    pub fn win_method(&self) -> WinMethod {
+        self.context.win_method
+    }
+
+    pub fn concealed(&self) -> &[Tile] {
+        &self.tiles[..14 - 3 * self.melds().count()]
+    }
 
-        let ks = tile_left
+    /// concealed tiles and tiles in melds
+    pub fn tiles(&self) -> Vec<Tile> {
+        self.concealed()
             .iter()
-            .filter_map(|(&k, &v)| if v > 0 { Some(k) } else { None })
-            .take(3)
+            .chain(self.melds().flat_map(|meld| meld.tiles()))
+            .copied()
+            .collect()
+    }
+
+    pub fn melds(&self) -> impl Iterator<Item = &Meld> {
+        self.melds.iter().flatten()
+    }
+
+    /// ankan keeps the hand closed
+    pub fn is_closed(&self) -> bool {
+        self.melds().all(|meld| !meld.is_open())
+    }
+
+    pub fn yakus(&self) -> Option<Vec<Yaku>> {
+        self.best_pattern().map(|(_, yakus)| yakus)
+    }
+
+    /// fu of the pattern chosen by `yakus`
+    pub fn fu(&self) -> Option<Fu> {
+        self.best_pattern().map(|(pattern, _)| pattern.fu())
+    }
+
+    /// the decomposition chosen by `yakus`
+    pub fn decomposition(&self) -> Option<Decomposition> {
+        self.best_pattern()
+            .map(|(pattern, _)| pattern.decomposition())
+    }
+
+    /// every way to read the hand, one for each block the last draw could complete
+    pub fn decompositions(&self) -> impl Iterator<Item = Decomposition> {
+        self.patterns()
+            .into_iter()
+            .map(|pattern| pattern.decomposition())
+    }
+
+    /// every wait the last draw could be read as
+    pub fn waits(&self) -> Vec<Wait> {
+        let mut waits = self
+            .patterns()
+            .iter()
+            .map(|pattern| pattern.wait())
             .collect::<Vec<_>>();
+        waits.sort();
+        waits.dedup();
+        waits
+    }
 
-        // continue with a pair
-        if pair_left > 0 && *tile_left.get(&ks[0]).unwrap() >= 2 {
-            let current = TileBlock::new_pair([ks[0]; 2]).unwrap();
-            *tile_left.get_mut(&ks[0]).unwrap() -= 2;
-            Self::find_common_patterns(tile_left, group_left, pair_left - 1)
-                .into_iter()
-                .map(|mut v| {
-                    v.push(current);
-                    v
-                })
-                .for_each(|v| ret.push(v));
-            *tile_left.get_mut(&ks[0]).unwrap() += 2;
-        }
-
-        // continue with a triplet
-        if group_left > 0 && *tile_left.get(&ks[0]).unwrap() >= 3 {
-            let current = TileBlock::new_triplet([ks[0]; 3]).unwrap();
-            *tile_left.get_mut(&ks[0]).unwrap() -= 3;
-            Self::find_common_patterns(tile_left, group_left - 1, pair_left)
-                .into_iter()
-                .map(|mut v| {
-                    v.push(current);
-                    v
-                })
-                .for_each(|v| ret.push(v));
-            *tile_left.get_mut(&ks[0]).unwrap() += 3;
-        }
-
-        // continue with a sequence
-        if group_left > 0 && ks.len() >= 3 {
-            if let Ok(current) = TileBlock::new_sequence([ks[0], ks[1], ks[2]]) {
-                *tile_left.get_mut(&current.tiles()[0]).unwrap() -= 1;
-                *tile_left.get_mut(&current.tiles()[1]).unwrap() -= 1;
-                *tile_left.get_mut(&current.tiles()[2]).unwrap() -= 1;
-                Self::find_common_patterns(tile_left, group_left - 1, pair_left)
-                    .into_iter()
-                    .map(|mut v| {
-                        v.push(current);
-                        v
+    /// ranked by points, then han, then fu, the first pattern wins a full tie
+    fn best_pattern(&self) -> Option<(TilePattern, Vec<Yaku>)> {
+        self.patterns()
+            .into_iter()
+            .rev()
+            .flat_map(|pattern| {
+                // mangan renhou is a limit of its own, other yaku don't count with it
+                let renhou = pattern
+                    .is_renhou(RenhouRule::Mangan)
+                    .then(|| (pattern.clone(), vec![Yaku::Renhou]));
+                let yakus = pattern.yakus();
+                [(pattern, yakus)].into_iter().chain(renhou)
+            })
+            .map(|(pattern, yakus)| {
+                let rank = self.score(&pattern, &yakus).map(|points| {
+                    (
+                        points.payment.total(),
+                        self.han(&yakus),
+                        pattern.fu().total(),
+                    )
+                });
+                (rank, pattern, yakus)
+            })
+            .max_by(|(lhs, ..), (rhs, ..)| lhs.cmp(rhs))
+            .map(|(_, pattern, yakus)| (pattern, yakus))
+    }
+
+    /// a red five is only discarded when asked for or when no normal one left
+    pub fn discard(self, tile: Tile) -> Result<ReadyTileSet> {
+        let mut tiles = self.tiles;
+        let concealed = self.concealed();
+        if let Some(index) = concealed
+            .iter()
+            .position(|&t| t == tile && t.is_red() == tile.is_red())
+            .or_else(|| concealed.iter().position(|&t| t == tile))
+        {
+            tiles[index..].rotate_left(1);
+            tiles[13] = T_INVALID;
+            Ok(ReadyTileSet {
+                tiles,
+                melds: self.melds,
+                context: WinContext {
+                    situation: WinSituation::default(),
+                    ..self.context
+                },
+            })
+        } else {
+            Err(Error::NoSuchTile(tile))
+        }
+    }
+
+    /// every distinct discard ranked by shanten, then ukeire, then tenpai value,
+    /// then the han reachable within `budget` for each discard,
+    /// `visible` counts every tile seen including this hand as in `tile_counts`
+    pub fn discard_options(&self, visible: &[u8; 34], budget: Budget) -> Vec<DiscardOption> {
+        let mut discards: Vec<Tile> = vec![];
+        for &tile in self.concealed() {
+            if !discards
+                .iter()
+                .any(|&other| other == tile && other.is_red() == tile.is_red())
+            {
+                discards.push(tile);
+            }
+        }
+
+        let mut options = discards
+            .into_iter()
+            .map(|discard| {
+                let ready_set = self.discard(discard).unwrap();
+                let shanten = ready_set.shanten();
+                let ukeire = ready_set.ukeire(visible);
+                let tenpai_value = (shanten == 0).then(|| {
+                    ukeire
+                        .tiles
+                        .iter()
+                        .filter(|&&(_, unseen)| unseen > 0)
+                        .filter_map(|&(tile, _)| ready_set.ron(tile).points())
+                        .map(|points| points.total())
+                        .max()
+                        .unwrap_or(0)
+                });
+                // a tenpai hand already has its value in points
+                let search = if shanten > 0 {
+                    ready_set.search(budget)
+                } else {
+                    Search::default()
+                };
+                let reachable_han = search
+                    .wins
+                    .iter()
+                    .map(|(_, yakus)| {
+                        yakus
+                            .iter()
+                            .map(|yaku| yaku.han(ready_set.is_closed(), &self.context.rules))
+                            .sum()
                     })
-                    .for_each(|v| ret.push(v));
-                *tile_left.get_mut(&current.tiles()[0]).unwrap() += 1;
-                *tile_left.get_mut(&current.tiles()[1]).unwrap() += 1;
-                *tile_left.get_mut(&current.tiles()[2]).unwrap() += 1;
+                    .max();
+                DiscardOption {
+                    discard,
+                    shanten,
+                    ukeire,
+                    tenpai_value,
+                    reachable_han,
+                    exhausted: search.exhausted,
+                }
+            })
+            .collect::<Vec<_>>();
+        DiscardOption::rank(&mut options);
+        options
+    }
+
+    fn check_situation(&self) -> Result<()> {
+        let situation = self.context.situation;
+        let is_tsumo = self.win_method() == WinMethod::Tsumo;
+        if (situation.haitei || situation.rinshan) && !is_tsumo {
+            return Err(SituationConflict::RonOnDraw.into());
+        }
+        if situation.first_draw && !is_tsumo && self.context.is_dealer() {
+            return Err(SituationConflict::DealerFirstDrawRon.into());
+        }
+        if (situation.houtei || situation.chankan) && is_tsumo {
+            return Err(SituationConflict::TsumoOnDiscard.into());
+        }
+        if situation.haitei && situation.rinshan {
+            return Err(SituationConflict::HaiteiRinshan.into());
+        }
+        if situation.rinshan && !self.melds().any(|meld| meld.is_kan()) {
+            return Err(SituationConflict::RinshanWithoutKan.into());
+        }
+        if situation.houtei && situation.chankan {
+            return Err(SituationConflict::HouteiChankan.into());
+        }
+        if situation.first_draw
+            && (self.melds().next().is_some()
+                || self.context.riichi.is_some()
+                || situation.haitei
+                || situation.rinshan)
+        {
+            return Err(SituationConflict::FirstDrawInterrupted.into());
+        }
+        Ok(())
+    }
+
+    /// all possible patterns
+    fn patterns(&self) -> Vec<TilePattern> {
+        let meld_blocks = self.melds().map(|meld| meld.block()).collect::<Vec<_>>();
+        let mut patterns = vec![];
+        for pattern in find_blocks(self.concealed(), &meld_blocks) {
+            self.push_readings(&mut patterns, pattern);
+        }
+        patterns.sort();
+        patterns.dedup();
+        patterns
+    }
+
+    /// one pattern for each concealed block the last draw could complete
+    fn push_readings(&self, patterns: &mut Vec<TilePattern>, pattern: Vec<TileBlock>) {
+        for (index, block) in pattern.iter().enumerate() {
+            if !block.is_open()
+                && block.tiles().contains(&self.last_draw)
+                && (index == 0 || pattern[index - 1] != *block)
+            {
+                patterns.push(TilePattern::new(
+                    pattern.clone(),
+                    self.last_draw,
+                    index,
+                    self.context,
+                ));
             }
         }
-        ret
     }
 }
 
@@ -178,7 +483,7 @@ mod tests {
 
     #[test]
     fn kukoshi_pattern() {
-        let tileset = ReadyTileSet::from_str("19p 19s 19m haku hatsu chun tonn nan shaa pei")
+        let tileset = ReadyTileSet::from_str("19p 19s 19m haku hatsu chun ton nan shaa pei")
             .unwrap()
             .draw(T_CHUN);
         let patterns = tileset.patterns();
@@ -217,10 +522,14 @@ mod tests {
             .unwrap()
             .draw(T_2P);
         let patterns = tileset.patterns();
-        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns.len(), 4);
         assert_eq!(patterns[0].pattern.len(), 5);
         assert_eq!(patterns[1].pattern.len(), 5);
         assert_eq!(patterns[0].last_draw, T_2P);
+        assert_eq!(
+            tileset.waits(),
+            vec![Wait::Ryanmen, Wait::Kanchan, Wait::Shanpon]
+        );
 
         let tileset = ReadyTileSet::from_str("1p3 2p3 3p3 4p3 haku")
             .unwrap()
@@ -233,6 +542,687 @@ mod tests {
         assert_eq!(patterns[0].last_draw, T_HAKU.clone());
     }
 
+    #[test]
+    fn open_pattern() {
+        let tileset = ReadyTileSet::new(
+            &[T_4P, T_5P, T_6P, T_7P, T_8P, T_9P, T_9P],
+            &[
+                Meld::chi([T_1P, T_2P, T_3P]).unwrap(),
+                Meld::pon(T_HAKU, RelativeSeat::Toimen).unwrap(),
+            ],
+        )
+        .unwrap()
+        .draw(T_9P);
+        let patterns = tileset.patterns();
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns[0].pattern.len(), 5);
+        assert_eq!(tileset.waits(), vec![Wait::Ryanmen, Wait::Tanki]);
+        assert_eq!(
+            tileset.yakus(),
+            Some(vec![
+                Yaku::Honiisou,
+                Yaku::YakuhaiSangenpai(T_HAKU),
+                Yaku::Ikkitsuukan
+            ])
+        );
+
+        let tileset = ReadyTileSet::new(
+            &[T_2P, T_3P, T_4P, T_5M, T_6M, T_7M, T_3S, T_4S, T_5S, T_3P],
+            &[Meld::chi([T_7S, T_8S, T_9S]).unwrap()],
+        )
+        .unwrap()
+        .draw(T_3P);
+        assert_eq!(tileset.yakus(), Some(vec![]));
+    }
+
+    #[test]
+    fn kan_pattern() {
+        let concealed = [T_HAKU, T_HAKU, T_HAKU, T_CHUN, T_CHUN, T_CHUN, T_2S];
+        let tileset = ReadyTileSet::new(
+            &concealed,
+            &[Meld::ankan(T_1M).unwrap(), Meld::ankan(T_9P).unwrap()],
+        )
+        .unwrap()
+        .draw(T_2S);
+        assert!(tileset.is_closed());
+        let patterns = tileset.patterns();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].pattern.len(), 5);
+        assert_eq!(tileset.yakus(), Some(vec![Yaku::Suuankoutanki]));
+
+        let tileset = ReadyTileSet::new(
+            &concealed,
+            &[
+                Meld::ankan(T_1M).unwrap(),
+                Meld::daiminkan(T_9P, RelativeSeat::Toimen).unwrap(),
+            ],
+        )
+        .unwrap()
+        .draw(T_2S);
+        assert!(!tileset.is_closed());
+        assert_eq!(
+            tileset.yakus(),
+            Some(vec![
+                Yaku::YakuhaiSangenpai(T_HAKU),
+                Yaku::YakuhaiSangenpai(T_CHUN),
+                Yaku::Toitoihou,
+                Yaku::Sanankou
+            ])
+        );
+    }
+
+    #[test]
+    fn win_method() {
+        let tileset = ReadyTileSet::from_str("haku3 hatsu3 chun2 1p3 2p2").unwrap();
+        assert_eq!(
+            tileset.draw(T_CHUN).yakus(),
+            Some(vec![Yaku::Suuankou, Yaku::Daisangen])
+        );
+        let full_set = tileset.ron(T_CHUN);
+        assert_eq!(full_set.win_method(), WinMethod::Ron);
+        assert_eq!(full_set.yakus(), Some(vec![Yaku::Daisangen]));
+
+        let tileset = ReadyTileSet::from_str("123456789p 1234m").unwrap();
+        assert_eq!(
+            tileset.draw(T_1M).yakus(),
+            Some(vec![Yaku::MenzenchinTsumohou, Yaku::Ikkitsuukan])
+        );
+        assert_eq!(tileset.ron(T_1M).yakus(), Some(vec![Yaku::Ikkitsuukan]));
+    }
+
+    #[test]
+    fn winds() {
+        // south seat of the east round unless set
+        let tileset = ReadyTileSet::from_str("ton3 nan3 123p 456s 9m").unwrap();
+        assert_eq!(
+            tileset.draw(T_9M).yakus(),
+            Some(vec![
+                Yaku::MenzenchinTsumohou,
+                Yaku::YakuhaiJikaze(Wind::Nan),
+                Yaku::YakuhaiBakaze(Wind::Ton)
+            ])
+        );
+        let tileset = tileset.with_winds(Wind::Nan, Wind::Nan);
+        assert_eq!(
+            tileset.ron(T_9M).yakus(),
+            Some(vec![
+                Yaku::YakuhaiJikaze(Wind::Nan),
+                Yaku::YakuhaiBakaze(Wind::Nan)
+            ])
+        );
+        let full_set = tileset.ron(T_9M).with_winds(Wind::Shaa, Wind::Pei);
+        assert_eq!(full_set.yakus(), Some(vec![]));
+    }
+
+    #[test]
+    fn riichi() {
+        let tileset = ReadyTileSet::from_str("ton3 nan3 123p 456s 9m")
+            .unwrap()
+            .with_winds(Wind::Shaa, Wind::Pei);
+        assert_eq!(tileset.ron(T_9M).yakus(), Some(vec![]));
+        assert_eq!(
+            tileset
+                .with_riichi(Riichi::Riichi, false)
+                .unwrap()
+                .ron(T_9M)
+                .yakus(),
+            Some(vec![Yaku::Riichi])
+        );
+        assert_eq!(
+            tileset
+                .ron(T_9M)
+                .with_riichi(Riichi::DoubleRiichi, true)
+                .unwrap()
+                .yakus(),
+            Some(vec![Yaku::DoubleRiichi, Yaku::Ippatsu])
+        );
+
+        let tileset = ReadyTileSet::new(
+            &[
+                T_TON, T_TON, T_TON, T_1P, T_2P, T_3P, T_4S, T_5S, T_6S, T_9M,
+            ],
+            &[Meld::pon(T_NAN, RelativeSeat::Toimen).unwrap()],
+        )
+        .unwrap();
+        assert_eq!(
+            tileset.with_riichi(Riichi::Riichi, false).unwrap_err(),
+            Error::OpenRiichi
+        );
+        assert!(tileset
+            .ron(T_9M)
+            .with_riichi(Riichi::Riichi, false)
+            .is_err());
+    }
+
+    #[test]
+    fn situation() {
+        let tileset = ReadyTileSet::from_str("ton3 nan3 123p 456s 9m")
+            .unwrap()
+            .with_winds(Wind::Shaa, Wind::Pei);
+        let haitei = WinSituation {
+            haitei: true,
+            ..Default::default()
+        };
+        let houtei = WinSituation {
+            houtei: true,
+            ..Default::default()
+        };
+        let rinshan = WinSituation {
+            rinshan: true,
+            ..Default::default()
+        };
+        let chankan = WinSituation {
+            chankan: true,
+            ..Default::default()
+        };
+        let first_draw = WinSituation {
+            first_draw: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tileset.draw(T_9M).with_situation(haitei).unwrap().yakus(),
+            Some(vec![Yaku::Haiteiraoyue, Yaku::MenzenchinTsumohou])
+        );
+        assert_eq!(
+            tileset.ron(T_9M).with_situation(houtei).unwrap().yakus(),
+            Some(vec![Yaku::Houteiraoyui])
+        );
+        let kan = ReadyTileSet::from_str("[ton4] nan3 123p 456s 9m")
+            .unwrap()
+            .with_winds(Wind::Shaa, Wind::Pei);
+        assert_eq!(
+            kan.draw(T_9M).with_situation(rinshan).unwrap().yakus(),
+            Some(vec![Yaku::Rinshankaihou, Yaku::MenzenchinTsumohou])
+        );
+        assert_eq!(
+            tileset.ron(T_9M).with_situation(chankan).unwrap().yakus(),
+            Some(vec![Yaku::Chankan])
+        );
+        assert_eq!(
+            tileset
+                .draw(T_9M)
+                .with_situation(first_draw)
+                .unwrap()
+                .yakus(),
+            Some(vec![Yaku::Chiihou])
+        );
+        assert_eq!(
+            tileset
+                .with_winds(Wind::Ton, Wind::Ton)
+                .draw(T_9M)
+                .with_situation(first_draw)
+                .unwrap()
+                .yakus(),
+            Some(vec![Yaku::Tenhou])
+        );
+
+        assert_eq!(
+            tileset.ron(T_9M).with_situation(haitei),
+            Err(Error::InvalidSituation(SituationConflict::RonOnDraw))
+        );
+        assert_eq!(
+            tileset.draw(T_9M).with_situation(houtei),
+            Err(SituationConflict::TsumoOnDiscard.into())
+        );
+        assert!(kan.ron(T_9M).with_situation(rinshan).is_err());
+        assert_eq!(
+            tileset.draw(T_9M).with_situation(rinshan),
+            Err(SituationConflict::RinshanWithoutKan.into())
+        );
+        let pons = ReadyTileSet::new(
+            &[T_1P, T_2P, T_3P, T_4S, T_5S, T_6S, T_9M],
+            &[
+                Meld::pon(T_TON, RelativeSeat::Toimen).unwrap(),
+                Meld::pon(T_NAN, RelativeSeat::Kamicha).unwrap(),
+            ],
+        )
+        .unwrap();
+        assert!(pons.draw(T_9M).with_situation(rinshan).is_err());
+        let open_kan = ReadyTileSet::new(
+            &[T_1P, T_2P, T_3P, T_4S, T_5S, T_6S, T_9M],
+            &[
+                Meld::pon(T_TON, RelativeSeat::Toimen).unwrap(),
+                Meld::pon(T_NAN, RelativeSeat::Kamicha)
+                    .unwrap()
+                    .add_kan()
+                    .unwrap(),
+            ],
+        )
+        .unwrap();
+        assert!(open_kan.draw(T_9M).with_situation(rinshan).is_ok());
+        assert!(tileset.draw(T_9M).with_situation(chankan).is_err());
+        assert!(tileset
+            .with_winds(Wind::Ton, Wind::Ton)
+            .ron(T_9M)
+            .with_situation(first_draw)
+            .is_err());
+        assert!(tileset
+            .draw(T_9M)
+            .with_situation(WinSituation {
+                haitei: true,
+                rinshan: true,
+                ..Default::default()
+            })
+            .is_err());
+        assert!(tileset
+            .with_riichi(Riichi::DoubleRiichi, true)
+            .unwrap()
+            .draw(T_9M)
+            .with_situation(first_draw)
+            .is_err());
+        assert!(ReadyTileSet::new(
+            &[T_1P, T_2P, T_3P, T_4S, T_5S, T_6S, T_9M],
+            &[Meld::ankan(T_TON).unwrap(), Meld::ankan(T_NAN).unwrap()],
+        )
+        .unwrap()
+        .draw(T_9M)
+        .with_situation(first_draw)
+        .is_err());
+    }
+
+    #[test]
+    fn rules() {
+        let tileset = ReadyTileSet::new(
+            &[T_2P, T_3P, T_4P, T_4S, T_5S, T_6S, T_8M],
+            &[
+                Meld::chi([T_2M, T_3M, T_4M]).unwrap(),
+                Meld::pon(T_6P, RelativeSeat::Toimen).unwrap(),
+            ],
+        )
+        .unwrap()
+        .ron(T_8M);
+        assert_eq!(tileset.yakus(), Some(vec![Yaku::Tanyao]));
+        let no_kuitan = Rules {
+            kuitan: false,
+            ..Default::default()
+        };
+        assert_eq!(tileset.with_rules(no_kuitan).yakus(), Some(vec![]));
+
+        let tileset = ReadyTileSet::from_str("23p 5p2 567m 345s 789s").unwrap();
+        assert_eq!(tileset.draw(T_4P).fu().unwrap().total(), 20);
+        let no_pinfu_tsumo = Rules {
+            pinfu_tsumo: false,
+            ..Default::default()
+        };
+        let tileset = tileset.with_rules(no_pinfu_tsumo).draw(T_4P);
+        assert_eq!(tileset.yakus(), Some(vec![Yaku::MenzenchinTsumohou]));
+        assert_eq!(tileset.fu().unwrap().total(), 30);
+
+        let tileset = ReadyTileSet::from_str("234p 1m3 345s 78s ton2")
+            .unwrap()
+            .with_winds(Wind::Ton, Wind::Ton);
+        assert_eq!(tileset.ron(T_9S).fu().unwrap().total(), 50);
+        let two_fu = Rules {
+            double_wind_pair_fu: DoubleWindPairFu::Two,
+            ..Default::default()
+        };
+        assert_eq!(
+            tileset.with_rules(two_fu).ron(T_9S).fu().unwrap().total(),
+            40
+        );
+
+        let tileset = ReadyTileSet::from_str("234p 567m 345s 789s 3p")
+            .unwrap()
+            .with_winds(Wind::Ton, Wind::Nan);
+        let first_draw = WinSituation {
+            first_draw: true,
+            ..Default::default()
+        };
+        // two dora would make 5 han a haneman
+        let renhou = |renhou| {
+            tileset
+                .with_rules(Rules {
+                    renhou,
+                    ..Default::default()
+                })
+                .ron(T_3P)
+                .with_situation(first_draw)
+                .unwrap()
+                .with_dora(&[T_2P, T_2P], &[])
+                .unwrap()
+                .result()
+                .unwrap()
+        };
+        assert!(!renhou(RenhouRule::Disabled).is_win());
+        let WinResult::Win(win) = renhou(RenhouRule::Mangan) else {
+            panic!("should be a win");
+        };
+        assert_eq!(win.yakus, vec![(Yaku::Renhou, Han::new(5))]);
+        assert_eq!(win.han, Han::new(5));
+        assert_eq!(win.points.limit, Some(Limit::Mangan));
+        assert_eq!(win.points.payment, Payment::Ron(8000));
+        let WinResult::Win(win) = renhou(RenhouRule::Yakuman) else {
+            panic!("should be a win");
+        };
+        assert_eq!(win.points.limit, Some(Limit::Yakuman(1)));
+
+        // a normal reading worth more than mangan beats renhou
+        let WinResult::Win(win) = ReadyTileSet::from_str("1123345678999p")
+            .unwrap()
+            .with_rules(Rules {
+                renhou: RenhouRule::Mangan,
+                ..Default::default()
+            })
+            .ron(T_2P)
+            .with_situation(first_draw)
+            .unwrap()
+            .result()
+            .unwrap()
+        else {
+            panic!("should be a win");
+        };
+        assert!(!win.yakus.iter().any(|&(yaku, _)| yaku == Yaku::Renhou));
+        assert_eq!(win.points.payment, Payment::Ron(16000));
+    }
+
+    #[test]
+    fn dora() {
+        let tileset = ReadyTileSet::new(
+            &[T_1P, T_2P, T_3P, T_4S, T_5S, T_6S, T_9M],
+            &[
+                Meld::pon(T_TON, RelativeSeat::Toimen).unwrap(),
+                Meld::ankan(T_NAN).unwrap(),
+            ],
+        )
+        .unwrap()
+        .with_winds(Wind::Shaa, Wind::Pei);
+        let full_set = tileset
+            .ron(T_9M)
+            .with_dora(&[T_PEI, T_TON], &[T_8M])
+            .unwrap();
+        assert_eq!(full_set.yakus(), Some(vec![]));
+        assert_eq!(
+            full_set.dora(),
+            Dora {
+                dora: 7,
+                ura_dora: 0,
+                aka_dora: 0
+            }
+        );
+
+        let full_set = ReadyTileSet::from_str("ton3 nan3 123p 456s 9m")
+            .unwrap()
+            .with_riichi(Riichi::Riichi, false)
+            .unwrap()
+            .draw(T_9M)
+            .with_dora(&[T_8M], &[T_9P, T_3S])
+            .unwrap();
+        assert_eq!(
+            full_set.dora(),
+            Dora {
+                dora: 2,
+                ura_dora: 2,
+                aka_dora: 0
+            }
+        );
+        assert_eq!(full_set.dora().total(), 4);
+        assert_eq!(
+            full_set.with_dora(&[T_1M; 6], &[]),
+            Err(Error::TooManyDoraIndicators(6))
+        );
+        assert_eq!(
+            full_set.discard(T_CHUN).unwrap_err(),
+            Error::NoSuchTile(T_CHUN)
+        );
+    }
+
+    #[test]
+    fn red_five() {
+        let tileset = ReadyTileSet::new(
+            &[T_0P, T_6P, T_7P, T_1S, T_2S, T_3S, T_5S, T_6S, T_7S, T_9M],
+            &[Meld::pon(T_0M, RelativeSeat::Toimen).unwrap()],
+        )
+        .unwrap();
+        let full_set = tileset.draw(T_9M).with_dora(&[T_4P], &[]).unwrap();
+        assert_eq!(full_set.yakus(), Some(vec![]));
+        assert_eq!(
+            full_set.dora(),
+            Dora {
+                dora: 1,
+                ura_dora: 0,
+                aka_dora: 2
+            }
+        );
+
+        let full_set = tileset.draw(T_0S);
+        assert_eq!(full_set.dora().aka_dora, 3);
+        let discarded = full_set.discard(T_5S).unwrap();
+        assert_eq!(
+            discarded.to_string(),
+            "9m 0p 6p 7p 1s 2s 3s 0s 6s 7s [0m 5m 5m]"
+        );
+        let discarded = full_set.discard(T_0P).unwrap();
+        assert_eq!(
+            discarded.to_string(),
+            "9m 6p 7p 1s 2s 3s 0s 5s 6s 7s [0m 5m 5m]"
+        );
+    }
+
+    #[test]
+    fn extra_red_five() {
+        let error = ReadyTileSet::from_str("0000m 123p 456s 789s").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::ExtraRedFive(T_0M));
+        assert_eq!(error.span, 0..5);
+        let error = ReadyTileSet::from_mpsz("05m0p0s 123p 456s 789s 0m").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::ExtraRedFive(T_0M));
+        assert_eq!(error.span, 23..24);
+        assert!(FullTileSet::from_mpsz("123m406p789s1112z+0p").is_err());
+        assert!(ReadyTileSet::from_str("0m 0p 0s 123p 456s 789m ton").is_ok());
+
+        assert_eq!(
+            ReadyTileSet::new(
+                &[T_0P, T_6P, T_7P, T_1S, T_2S, T_3S, T_5S, T_6S, T_7S, T_0M],
+                &[Meld::pon(T_0M, RelativeSeat::Toimen).unwrap()],
+            )
+            .unwrap_err(),
+            Error::ExtraRedFive(T_0M)
+        );
+    }
+
+    #[test]
+    fn fu() {
+        let tileset = ReadyTileSet::from_str("234p 567m 345s 789s 3p").unwrap();
+        assert_eq!(tileset.ron(T_3P).fu().unwrap().total(), 40);
+        assert_eq!(tileset.draw(T_3P).fu().unwrap().total(), 30);
+        let tileset = ReadyTileSet::from_str("1p2 2s2 3m2 4p2 5s2 6m2 7p").unwrap();
+        assert_eq!(tileset.ron(T_7P).fu().unwrap().total(), 25);
+        let tileset = ReadyTileSet::from_str("124578p 124578m 1s").unwrap();
+        assert_eq!(tileset.draw(T_1S).fu(), None);
+    }
+
+    #[test]
+    fn best_pattern() {
+        // tanki gives 2 more fu than ryanmen and rounds up to 40
+        let tileset = ReadyTileSet::from_str("1m3 789s 234s 3455p")
+            .unwrap()
+            .draw(T_5P);
+        assert_eq!(tileset.waits(), vec![Wait::Ryanmen, Wait::Tanki]);
+        assert_eq!(tileset.fu().unwrap().total(), 40);
+        let decomposition = tileset.decomposition().unwrap();
+        assert_eq!(
+            decomposition.to_string(),
+            "[1m 1m 1m] [3p 4p 5p] [5p 5p] [2s 3s 4s] [7s 8s 9s]"
+        );
+        assert_eq!(decomposition.win_block().unwrap().kind(), BlockKind::Pair);
+        assert_eq!(decomposition.win_tile(), Some(T_5P));
+        assert_eq!(decomposition.wait(), Some(Wait::Tanki));
+        assert_eq!(tileset.decompositions().count(), 2);
+
+        let tileset = ReadyTileSet::from_str("112233p 445566s 7m")
+            .unwrap()
+            .ron(T_7M);
+        assert_eq!(tileset.yakus(), Some(vec![Yaku::Ryanpeikou]));
+        assert_eq!(tileset.points().unwrap().payment, Payment::Ron(5200));
+
+        let tileset = ReadyTileSet::from_str("124578p 124578m 1s").unwrap();
+        assert_eq!(tileset.draw(T_1S).decomposition(), None);
+
+        let tileset = FullTileSet::from_mpsz("505m123p456s789s1z+1z").unwrap();
+        assert_eq!(
+            tileset.decomposition().unwrap().blocks()[0].to_string(),
+            "[0m 5m 5m]"
+        );
+    }
+
+    #[test]
+    fn points() {
+        let tileset = ReadyTileSet::from_str("234p 567m 345s 789s 3p")
+            .unwrap()
+            .with_winds(Wind::Ton, Wind::Nan);
+        assert_eq!(tileset.ron(T_3P).points(), None);
+        let points = tileset
+            .with_riichi(Riichi::Riichi, false)
+            .unwrap()
+            .ron(T_3P)
+            .with_dora(&[T_1P], &[T_6M])
+            .unwrap()
+            .with_sticks(1, 2)
+            .points()
+            .unwrap();
+        assert_eq!(points.payment, Payment::Ron(5200 + 300));
+        assert_eq!(points.total(), 7500);
+
+        let points = ReadyTileSet::from_str("haku3 hatsu3 chun2 1p3 2p2")
+            .unwrap()
+            .ron(T_CHUN)
+            .points()
+            .unwrap();
+        assert_eq!(points.limit, Some(Limit::Yakuman(1)));
+        assert_eq!(points.payment, Payment::Ron(32000));
+    }
+
+    #[test]
+    fn discard_options() {
+        let full_set = ReadyTileSet::from_str("123p 456m 789s 1s2 45p")
+            .unwrap()
+            .with_winds(Wind::Ton, Wind::Nan)
+            .draw(T_CHUN);
+        let visible = tile_counts(full_set.concealed());
+        let options = full_set.discard_options(&visible, Budget::default());
+        assert_eq!(options.len(), 13);
+        assert_eq!(options[0].discard, T_CHUN);
+        assert_eq!(options[0].shanten, 0);
+        assert_eq!(options[0].ukeire.total, 7);
+        assert_eq!(options[0].tenpai_value, Some(1000));
+        assert_eq!(
+            options[0].to_string(),
+            "discard chun: tenpai, 7 tiles of 2 kinds (3px3 6px4), up to 1000 points"
+        );
+        assert_eq!(options[0].reachable_han, None);
+        assert!(options[1..].iter().all(|option| option.shanten == 1));
+        assert_eq!(options[1].discard, T_1P);
+        assert_eq!(options[1].reachable_han, Some(Han::new(2)));
+        assert!(!options[1].exhausted);
+        assert_eq!(
+            options[1].to_string(),
+            "discard 1p: 1 shanten, 28 tiles of 9 kinds \
+             (1px3 2px3 3px3 4px3 5px3 6px4 7px4 1sx2 chunx3), reaching 2 han"
+        );
+        let options = full_set.discard_options(&visible, Budget::Nodes(0));
+        assert!(options[1].exhausted);
+        assert!(options
+            .windows(2)
+            .all(|pair| pair[0].ukeire.total >= pair[1].ukeire.total || pair[0].shanten == 0));
+
+        // both keep the 6s 9s wait, only one keeps tanyao
+        let full_set = ReadyTileSet::from_str("234p 567m 345s 6s2 78s")
+            .unwrap()
+            .with_winds(Wind::Ton, Wind::Nan)
+            .draw(T_1P);
+        let options =
+            full_set.discard_options(&tile_counts(full_set.concealed()), Budget::default());
+        assert_eq!(options[0].discard, T_1P);
+        assert_eq!(options[0].tenpai_value, Some(2000));
+        assert_eq!(options[1].discard, T_4P);
+        assert_eq!(options[1].tenpai_value, Some(1000));
+        assert_eq!(options[0].ukeire, options[1].ukeire);
+    }
+
+    #[test]
+    fn mpsz() {
+        let full_set = FullTileSet::from_mpsz("123m406p789s1112z+2z").unwrap();
+        assert_eq!(full_set.last_draw, T_NAN);
+        assert_eq!(full_set.to_mpsz(), "123m406p789s1112z+2z");
+        assert_eq!(full_set.dora().aka_dora, 1);
+        assert_eq!(
+            FullTileSet::from_mpsz("123m406p789s11122z").unwrap(),
+            full_set
+        );
+        let ready_set = full_set.discard(T_TON).unwrap();
+        assert_eq!(ready_set.to_mpsz(), "123m406p789s1122z");
+        assert_eq!(
+            ReadyTileSet::from_mpsz(&ready_set.to_mpsz())
+                .unwrap()
+                .to_string(),
+            ready_set.to_string()
+        );
+
+        let full_set = FullTileSet::from_mpsz("[1111m][9999p][1111z]789s5z+5z").unwrap();
+        assert_eq!(full_set.tiles().len(), 17);
+        assert_eq!(full_set.to_mpsz(), "789s5z[1111m][9999p][1111z]+5z");
+        let yakus = full_set.yakus().unwrap();
+        assert!(yakus.contains(&Yaku::Sankantsu));
+        assert!(yakus.contains(&Yaku::Sanankou));
+        assert_eq!(
+            FullTileSet::from_mpsz("789s5z[1111m]+[5555z]")
+                .unwrap_err()
+                .kind,
+            ParseErrorKind::WrongHandSize {
+                expected: 1,
+                found: 4
+            }
+        );
+
+        assert!(FullTileSet::from_mpsz("123m406p789s1112z").is_err());
+        assert!(FullTileSet::from_mpsz("123m406p789s111z+22z").is_err());
+        assert!(FullTileSet::from_mpsz("123m406p789s1112z+").is_err());
+    }
+
+    #[test]
+    fn result() {
+        let tileset = ReadyTileSet::from_str("234p 567m 345s 789s 3p")
+            .unwrap()
+            .with_winds(Wind::Ton, Wind::Nan);
+        let result = tileset.ron(T_3P).result().unwrap();
+        assert!(!result.is_win());
+        assert_eq!(
+            result.decomposition().to_string(),
+            "[5m 6m 7m] [2p 3p 4p] [3p 3p] [3s 4s 5s] [7s 8s 9s]"
+        );
+        assert!(matches!(
+            result,
+            WinResult::NoYaku {
+                wait: Wait::Kanchan,
+                ..
+            }
+        ));
+
+        let WinResult::Win(win) = tileset
+            .draw(T_3P)
+            .with_dora(&[T_2P], &[])
+            .unwrap()
+            .result()
+            .unwrap()
+        else {
+            panic!("should be a win");
+        };
+        assert_eq!(win.yakus, vec![(Yaku::MenzenchinTsumohou, Han::new(1))]);
+        assert_eq!(win.dora.dora, 3);
+        assert_eq!(win.han, Han::new(4));
+        assert_eq!(win.fu.total(), 30);
+        assert_eq!(win.points.limit, None);
+        assert_eq!(
+            win.points.payment,
+            Payment::Tsumo {
+                dealer: 3900,
+                non_dealer: 2000
+            }
+        );
+
+        let tileset = ReadyTileSet::from_str("124578p 124578m 1s").unwrap();
+        assert_eq!(tileset.draw(T_1S).result(), None);
+    }
+
     #[test]
     fn non_pattern() {
         let tileset = ReadyTileSet::from_str("124578p 124578m 1s")
@@ -1,13 +1,44 @@
-//! Closed hand only
-//! Focused on current tile set (No round wind, dora, discarded, etc)
+//! Closed hand, or open hand with chi / pon / kan
+//! Focused on current tile set, winds and dora (No discarded, etc)
 
+mod context;
+mod decomposition;
+mod discard;
+mod dora;
+mod error;
+mod fu;
 mod full_set;
+mod meld;
+mod notation;
 mod ready_set;
+mod rules;
+mod score;
+mod search;
+mod shanten;
 mod tile;
 mod tile_block;
 mod tile_pattern;
+mod ukeire;
+mod wait;
+mod win;
 mod yaku;
 
+pub use context::{Riichi, WinMethod, WinSituation};
+pub use decomposition::{decompose, Decomposition};
+pub use discard::DiscardOption;
+pub use dora::Dora;
+pub use error::{Error, ParseError, ParseErrorKind, SituationConflict};
+pub use fu::{Fu, FuItem};
+pub use full_set::FullTileSet;
+pub use meld::{Meld, MeldKind, RelativeSeat};
 pub use ready_set::ReadyTileSet;
+pub use rules::{DoubleWindPairFu, RenhouRule, Rules};
+pub use score::{Limit, Payment, Points};
+pub use search::{Budget, Search};
+pub use shanten::{chiitoitsu_shanten, kokushi_shanten, regular_shanten, shanten, tile_counts};
 pub use tile::*;
-pub use yaku::Yaku;
+pub use tile_block::{BlockKind, TileBlock};
+pub use ukeire::{ukeire, Ukeire};
+pub use wait::Wait;
+pub use win::{Win, WinResult};
+pub use yaku::{Han, Yaku};
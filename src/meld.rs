@@ -0,0 +1,202 @@
+use crate::{
+    error::{Error, Result},
+    tile::Tile,
+    tile_block::TileBlock,
+};
+use std::fmt::Display;
+
+/// where the called tile comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RelativeSeat {
+    /// 上家
+    Kamicha,
+    /// 对家
+    Toimen,
+    /// 下家
+    Shimocha,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MeldKind {
+    /// 吃
+    Chi,
+    /// 碰
+    Pon,
+    /// 暗杠
+    Ankan,
+    /// 大明杠
+    Daiminkan,
+    /// 加杠
+    Shouminkan,
+}
+
+/// a called block or a declared kan, fixed in the hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Meld {
+    kind: MeldKind,
+    block: TileBlock,
+    from: Option<RelativeSeat>,
+}
+
+impl Meld {
+    /// chi can only be called from kamicha
+    pub fn chi(mut tiles: [Tile; 3]) -> Result<Self> {
+        tiles.sort();
+        Ok(Meld {
+            kind: MeldKind::Chi,
+            block: TileBlock::new_sequence(tiles)?.into_open(),
+            from: Some(RelativeSeat::Kamicha),
+        })
+    }
+
+    /// pass a red five if one of the tiles is red, so as kans
+    pub fn pon(tile: Tile, from: RelativeSeat) -> Result<Self> {
+        Ok(Meld {
+            kind: MeldKind::Pon,
+            block: TileBlock::new_triplet(Self::same_tiles(tile))?.into_open(),
+            from: Some(from),
+        })
+    }
+
+    /// the only meld that keeps the hand closed
+    pub fn ankan(tile: Tile) -> Result<Self> {
+        Ok(Meld {
+            kind: MeldKind::Ankan,
+            block: TileBlock::new_quad(Self::same_tiles(tile))?,
+            from: None,
+        })
+    }
+
+    pub fn daiminkan(tile: Tile, from: RelativeSeat) -> Result<Self> {
+        Ok(Meld {
+            kind: MeldKind::Daiminkan,
+            block: TileBlock::new_quad(Self::same_tiles(tile))?.into_open(),
+            from: Some(from),
+        })
+    }
+
+    /// `from` is where the pon came from
+    pub fn shouminkan(tile: Tile, from: RelativeSeat) -> Result<Self> {
+        Ok(Meld {
+            kind: MeldKind::Shouminkan,
+            block: TileBlock::new_quad(Self::same_tiles(tile))?.into_open(),
+            from: Some(from),
+        })
+    }
+
+    /// extend a pon into a shouminkan
+    pub fn add_kan(self) -> Result<Self> {
+        match (self.kind, self.block.triplet(), self.from) {
+            (MeldKind::Pon, Some(tile), Some(from)) => Ok(Meld {
+                kind: MeldKind::Shouminkan,
+                block: TileBlock::new_quad([
+                    tile,
+                    self.tiles()[1],
+                    self.tiles()[2],
+                    tile.to_normal(),
+                ])?
+                .into_open(),
+                from: Some(from),
+            }),
+            _ => Err(Error::NotAPon(self)),
+        }
+    }
+
+    /// only the first one keeps the red five
+    fn same_tiles<const N: usize>(tile: Tile) -> [Tile; N] {
+        let mut tiles = [tile.to_normal(); N];
+        tiles[0] = tile;
+        tiles
+    }
+
+    pub fn kind(&self) -> MeldKind {
+        self.kind
+    }
+
+    /// `None` for ankan
+    pub fn from(&self) -> Option<RelativeSeat> {
+        self.from
+    }
+
+    pub fn is_kan(&self) -> bool {
+        self.block.quad().is_some()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.block.is_open()
+    }
+
+    pub fn tiles(&self) -> &[Tile] {
+        self.block.tiles()
+    }
+
+    pub(crate) fn block(&self) -> TileBlock {
+        self.block
+    }
+}
+
+impl Display for Meld {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        <TileBlock as Display>::fmt(&self.block, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn chi() {
+        let meld = Meld::chi([T_3P, T_1P, T_2P]).unwrap();
+        assert_eq!(meld.tiles(), [T_1P, T_2P, T_3P]);
+        assert_eq!(meld.from(), Some(RelativeSeat::Kamicha));
+        assert!(meld.block().is_open());
+        assert_eq!(meld.to_string(), "[1p 2p 3p]");
+
+        assert_eq!(
+            Meld::chi([T_1P, T_2P, T_4P]),
+            Err(Error::InvalidBlock(vec![T_1P, T_2P, T_4P]))
+        );
+        assert!(Meld::chi([T_8P, T_9P, T_1S]).is_err());
+        assert!(Meld::chi([T_TON, T_NAN, T_SHAA]).is_err());
+    }
+
+    #[test]
+    fn pon() {
+        let meld = Meld::pon(T_HAKU, RelativeSeat::Toimen).unwrap();
+        assert_eq!(meld.tiles(), [T_HAKU; 3]);
+        assert_eq!(meld.kind(), MeldKind::Pon);
+        assert_eq!(meld.from(), Some(RelativeSeat::Toimen));
+        assert!(meld.block().is_open());
+    }
+
+    #[test]
+    fn kan() {
+        let meld = Meld::ankan(T_1M).unwrap();
+        assert_eq!(meld.tiles(), [T_1M; 4]);
+        assert_eq!(meld.from(), None);
+        assert!(meld.is_kan());
+        assert!(!meld.is_open());
+
+        let meld = Meld::daiminkan(T_CHUN, RelativeSeat::Shimocha).unwrap();
+        assert!(meld.is_kan());
+        assert!(meld.is_open());
+
+        let meld = Meld::pon(T_5S, RelativeSeat::Kamicha)
+            .unwrap()
+            .add_kan()
+            .unwrap();
+        assert_eq!(meld.kind(), MeldKind::Shouminkan);
+        assert_eq!(meld.from(), Some(RelativeSeat::Kamicha));
+        assert_eq!(meld.tiles(), [T_5S; 4]);
+        assert_eq!(meld.add_kan(), Err(Error::NotAPon(meld)));
+
+        let meld = Meld::pon(T_0P, RelativeSeat::Kamicha)
+            .unwrap()
+            .add_kan()
+            .unwrap();
+        assert_eq!(meld.to_string(), "[0p 5p 5p 5p]");
+        assert!(Meld::chi([T_1P, T_2P, T_3P]).unwrap().add_kan().is_err());
+    }
+}
@@ -0,0 +1,412 @@
+use crate::{
+    error::{ParseError, ParseErrorKind},
+    meld::{Meld, MeldKind},
+    tile::Tile,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{ops::Range, str::FromStr};
+
+static HOUSE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"((ton|nan|shaa|pei|haku|chun|hatsu)|([0-9]+)([psm]))(\d+)?").unwrap()
+});
+
+/// a parsed tile and the bytes it was read from
+pub(crate) type Token = (Tile, Range<usize>);
+
+/// concealed tiles and ankan of a parsed hand, each kan as its 4 tiles
+#[derive(Debug, Default)]
+pub(crate) struct Tokens {
+    pub(crate) tiles: Vec<Token>,
+    pub(crate) kans: Vec<Vec<Token>>,
+}
+
+impl Tokens {
+    /// tiles in hand, a kan counted as 4 tiles
+    pub(crate) fn len(&self) -> usize {
+        self.tiles.len() + 4 * self.kans.len()
+    }
+
+    /// every tile including those of the kans
+    pub(crate) fn all(&self) -> Vec<Token> {
+        self.tiles
+            .iter()
+            .chain(self.kans.iter().flatten())
+            .cloned()
+            .collect()
+    }
+}
+
+/// house format such as `123p 4m3 haku`, a group may be repeated up to 4 times,
+/// an ankan is written in brackets as `[1111p]` or `[ton4]`
+pub(crate) fn parse_house(s: &str) -> Result<Tokens, ParseError> {
+    let mut tokens = Tokens::default();
+    for (start, word) in words(s) {
+        let Some(inner) = word.strip_prefix('[') else {
+            tokens.tiles.extend(parse_house_word(word, start)?);
+            continue;
+        };
+        let not_a_kan = || {
+            ParseError::new(
+                start..start + word.len(),
+                ParseErrorKind::NotAKan(word.to_string()),
+            )
+        };
+        let inner = inner.strip_suffix(']').ok_or_else(not_a_kan)?;
+        let group = parse_house_word(inner, start + 1)?;
+        if !is_kan(&group) {
+            return Err(not_a_kan());
+        }
+        tokens.kans.push(group);
+    }
+    Ok(tokens)
+}
+
+/// tiles of a word starting at byte `start`
+fn parse_house_word(word: &str, start: usize) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = vec![];
+    // every byte of a word must belong to a group
+    let mut end = 0;
+    for cap in HOUSE_REGEX.captures_iter(word) {
+        let group = cap.get(0).unwrap();
+        if group.start() != end {
+            break;
+        }
+        end = group.end();
+        let span = start + group.start()..start + group.end();
+        let tiles = if let Some(honor) = cap.get(2) {
+            vec![Tile::from_str(honor.as_str()).unwrap()]
+        } else {
+            cap[3]
+                .chars()
+                .map(|ch| Tile::from_str(&format!("{}{}", ch, &cap[4])).unwrap())
+                .collect()
+        };
+        let count = cap
+            .get(5)
+            .map_or(1, |count| count.as_str().parse().unwrap_or(usize::MAX));
+        if !(1..=4).contains(&count) {
+            return Err(ParseError::new(
+                span,
+                ParseErrorKind::CountOutOfRange {
+                    tile: tiles[0],
+                    count,
+                },
+            ));
+        }
+        for _ in 0..count {
+            tokens.extend(tiles.iter().map(|&tile| (tile, span.clone())));
+        }
+    }
+    if end != word.len() {
+        return Err(ParseError::unknown_tile(word).offset(start));
+    }
+    Ok(tokens)
+}
+
+/// tiles written as `123m406p789s1122z`, spaces between groups are allowed,
+/// an ankan is written in brackets as `[1111z]` or `[0555m]`
+pub(crate) fn parse_mpsz(s: &str) -> Result<Tokens, ParseError> {
+    let mut tokens = Tokens::default();
+    let mut digits: Vec<(usize, char)> = vec![];
+    // where the open bracket is, and the tiles after it
+    let mut kan: Option<(usize, Vec<Token>)> = None;
+    let unknown =
+        |span: Range<usize>| ParseError::unknown_tile(&s[span.clone()]).offset(span.start);
+    for (index, ch) in s.char_indices() {
+        match ch {
+            '0'..='9' => digits.push((index, ch)),
+            'm' | 'p' | 's' | 'z' => {
+                let group = digits.first().map_or(index, |&(start, _)| start)..index + 1;
+                if digits.is_empty() {
+                    return Err(unknown(group));
+                }
+                let tiles = match &mut kan {
+                    Some((_, tiles)) => tiles,
+                    None => &mut tokens.tiles,
+                };
+                for (start, digit) in digits.drain(..) {
+                    let tile = Tile::from_mpsz(&format!("{digit}{ch}"))
+                        .map_err(|_| unknown(group.clone()))?;
+                    tiles.push((tile, start..start + 1));
+                }
+            }
+            '[' if kan.is_none() && digits.is_empty() => kan = Some((index, vec![])),
+            ']' if digits.is_empty() => {
+                let Some((start, tiles)) = kan.take() else {
+                    return Err(unknown(index..index + 1));
+                };
+                if !is_kan(&tiles) {
+                    return Err(ParseError::new(
+                        start..index + 1,
+                        ParseErrorKind::NotAKan(s[start..index + 1].to_string()),
+                    ));
+                }
+                tokens.kans.push(tiles);
+            }
+            _ if ch.is_whitespace() => {
+                if let Some(&(start, _)) = digits.first() {
+                    return Err(unknown(start..index));
+                }
+            }
+            _ => return Err(unknown(index..index + ch.len_utf8())),
+        }
+    }
+    if let Some(&(start, _)) = digits.first() {
+        return Err(unknown(start..s.len()));
+    }
+    if let Some((start, _)) = kan {
+        return Err(ParseError::new(
+            start..s.len(),
+            ParseErrorKind::NotAKan(s[start..].to_string()),
+        ));
+    }
+    Ok(tokens)
+}
+
+/// 4 copies of the same tile
+fn is_kan(tiles: &[Token]) -> bool {
+    tiles.len() == 4 && tiles.iter().all(|(tile, _)| *tile == tiles[0].0)
+}
+
+/// tiles in the given order, the suit is written once for a run of the same suit
+pub(crate) fn format_mpsz(tiles: &[Tile]) -> String {
+    let mut text = String::new();
+    for (index, tile) in tiles.iter().enumerate() {
+        let mpsz = tile.to_mpsz();
+        let (number, suit) = mpsz.split_at(1);
+        text.push_str(number);
+        let next_suit = tiles.get(index + 1).map(|next| next.to_mpsz().split_off(1));
+        if next_suit.as_deref() != Some(suit) {
+            text.push_str(suit);
+        }
+    }
+    text
+}
+
+/// each ankan in brackets such as `[1111z]`, other melds are left out
+pub(crate) fn format_ankan<'a>(melds: impl Iterator<Item = &'a Meld>) -> String {
+    melds
+        .filter(|meld| meld.kind() == MeldKind::Ankan)
+        .map(|meld| format!("[{}]", format_mpsz(meld.tiles())))
+        .collect()
+}
+
+/// glyphs such as `🀇🀈🀉` or kanji such as `一萬 ⑨筒 赤五索 發`, spaces are optional
+pub(crate) fn parse_unicode(s: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = vec![];
+    let mut chars = s.char_indices().filter(|(_, ch)| !ch.is_whitespace());
+    while let Some((start, ch)) = chars.next() {
+        let mut end = start + ch.len_utf8();
+        if let Some(tile) = Tile::from_unicode(ch) {
+            tokens.push((tile, start..end));
+            continue;
+        }
+        let mut kanji = ch.to_string();
+        let mut take_next = |kanji: &mut String, end: &mut usize| {
+            if let Some((index, ch)) = chars.next() {
+                kanji.push(ch);
+                *end = index + ch.len_utf8();
+            }
+        };
+        if ch == '赤' {
+            take_next(&mut kanji, &mut end);
+        }
+        // a single kanji is an honor, otherwise a number is followed by its suit
+        if Tile::from_kanji(&kanji).is_err() {
+            take_next(&mut kanji, &mut end);
+        }
+        let tile = Tile::from_kanji(&kanji)
+            .map_err(|_| ParseError::unknown_tile(&s[start..end]).offset(start))?;
+        tokens.push((tile, start..end));
+    }
+    Ok(tokens)
+}
+
+/// no tile may appear more than 4 times, red fives count as fives,
+/// and each suit has only one red five
+pub(crate) fn check_copies(tokens: &[Token]) -> Result<(), ParseError> {
+    let Some((index, count)) = extra_copy(tokens.iter().map(|(tile, _)| *tile)) else {
+        return Ok(());
+    };
+    let (tile, span) = tokens[index].clone();
+    let kind = if count > 4 {
+        ParseErrorKind::CountOutOfRange { tile, count }
+    } else {
+        ParseErrorKind::ExtraRedFive(tile)
+    };
+    Err(ParseError::new(span, kind))
+}
+
+/// index of the first tile that is a fifth copy or a second red five of its suit,
+/// and the copies of it up to there
+pub(crate) fn extra_copy(tiles: impl IntoIterator<Item = Tile>) -> Option<(usize, usize)> {
+    let mut counts = [0; 34];
+    let mut reds = vec![];
+    for (index, tile) in tiles.into_iter().enumerate() {
+        counts[tile.index()] += 1;
+        if counts[tile.index()] > 4 {
+            return Some((index, counts[tile.index()]));
+        }
+        if tile.is_red() {
+            if reds.contains(&tile.suit()) {
+                return Some((index, counts[tile.index()]));
+            }
+            reds.push(tile.suit());
+        }
+    }
+    None
+}
+
+/// each word with its byte offset
+fn words(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.split_whitespace()
+        .map(move |word| (word.as_ptr() as usize - s.as_ptr() as usize, word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    fn tiles(tokens: Vec<Token>) -> Vec<Tile> {
+        tokens.into_iter().map(|(tile, _)| tile).collect()
+    }
+
+    fn error<T: std::fmt::Debug>(result: Result<T, ParseError>) -> (Range<usize>, ParseErrorKind) {
+        let error = result.unwrap_err();
+        (error.span, error.kind)
+    }
+
+    #[test]
+    fn house() {
+        let tokens = parse_house("123p 4m3 haku").unwrap().tiles;
+        assert_eq!(tokens[3], (T_4M, 5..8));
+        assert_eq!(tokens.len(), 7);
+        assert_eq!(
+            error(parse_house("19p tonn nan")),
+            (4..8, ParseErrorKind::UnknownTile("tonn".to_string()))
+        );
+        assert_eq!(
+            error(parse_house("1p haku0")),
+            (
+                3..8,
+                ParseErrorKind::CountOutOfRange {
+                    tile: T_HAKU,
+                    count: 0
+                }
+            )
+        );
+        assert_eq!(
+            error(parse_house("hatsu12")).1,
+            ParseErrorKind::CountOutOfRange {
+                tile: T_HATSU,
+                count: 12
+            }
+        );
+        assert!(parse_house("123 m").is_err());
+    }
+
+    #[test]
+    fn mpsz() {
+        let tokens = parse_mpsz("123m406p 789s1122z 7z").unwrap().tiles;
+        assert_eq!(tokens[4], (T_0P, 5..6));
+        let tiles = tiles(tokens);
+        assert_eq!(
+            tiles,
+            [
+                T_1M, T_2M, T_3M, T_4P, T_0P, T_6P, T_7S, T_8S, T_9S, T_TON, T_TON, T_NAN, T_NAN,
+                T_CHUN
+            ]
+        );
+        assert!(tiles[4].is_red());
+        assert_eq!(format_mpsz(&tiles), "123m406p789s11227z");
+
+        assert_eq!(
+            error(parse_mpsz("11m 123")),
+            (4..7, ParseErrorKind::UnknownTile("123".to_string()))
+        );
+        assert_eq!(error(parse_mpsz("12 3m")).0, 0..2);
+        assert_eq!(error(parse_mpsz("1m m")).0, 3..4);
+        assert_eq!(
+            error(parse_mpsz("1238z")),
+            (0..5, ParseErrorKind::UnknownTile("1238z".to_string()))
+        );
+        assert!(parse_mpsz("0z").is_err());
+        assert!(parse_mpsz("1x").is_err());
+        assert!(parse_mpsz("").unwrap().tiles.is_empty());
+    }
+
+    #[test]
+    fn kans() {
+        let tokens = parse_house("[1111p] [2p4] [ton4] 11112p [0555m]").unwrap();
+        assert_eq!(tiles(tokens.tiles.clone()), [T_1P, T_1P, T_1P, T_1P, T_2P]);
+        let kans = tokens.kans.iter().map(|kan| kan[0].0).collect::<Vec<_>>();
+        assert_eq!(kans, [T_1P, T_2P, T_TON, T_0M]);
+        assert!(kans[3].is_red());
+        assert_eq!(tokens.kans[1][3], (T_2P, 9..12));
+        assert_eq!(tokens.len(), 21);
+        // repeated groups without brackets are concealed tiles
+        let tokens = parse_house("1111m 234m 1m4").unwrap();
+        assert_eq!(tokens.tiles.len(), 11);
+        assert!(tokens.kans.is_empty());
+        assert_eq!(
+            error(parse_house("1m [1112p]")),
+            (3..10, ParseErrorKind::NotAKan("[1112p]".to_string()))
+        );
+        assert_eq!(error(parse_house("1m [1111p")).0, 3..9);
+        assert_eq!(error(parse_house("[1111x]")).0, 1..6);
+        assert!(parse_house("[1111p]]").is_err());
+
+        let tokens = parse_mpsz("123m[1111z] [0555p]9s").unwrap();
+        assert_eq!(tiles(tokens.tiles.clone()), [T_1M, T_2M, T_3M, T_9S]);
+        assert_eq!(tokens.kans[0][3], (T_TON, 8..9));
+        assert!(tokens.kans[1][0].0.is_red());
+        assert_eq!(tokens.all().len(), 12);
+        assert_eq!(
+            error(parse_mpsz("1m [1112z]")),
+            (3..10, ParseErrorKind::NotAKan("[1112z]".to_string()))
+        );
+        assert_eq!(error(parse_mpsz("1m [1111z")).0, 3..9);
+        assert_eq!(error(parse_mpsz("1m ]")).0, 3..4);
+        assert!(parse_mpsz("[[1111z]]").is_err());
+        assert!(parse_mpsz("11[11z]").is_err());
+        assert!(parse_mpsz("[1111]z").is_err());
+
+        let melds = [
+            Meld::ankan(T_0M).unwrap(),
+            Meld::pon(T_1P, RelativeSeat::Toimen).unwrap(),
+            Meld::ankan(T_HAKU).unwrap(),
+        ];
+        assert_eq!(format_ankan(melds.iter()), "[0555m][5555z]");
+    }
+
+    #[test]
+    fn unicode() {
+        assert_eq!(
+            tiles(parse_unicode("🀇🀈🀉 🀙🀄").unwrap()),
+            [T_1M, T_2M, T_3M, T_1P, T_CHUN]
+        );
+        let tiles = tiles(parse_unicode("一萬二万 赤⑤筒九索東発").unwrap());
+        assert_eq!(tiles, [T_1M, T_2M, T_5P, T_9S, T_TON, T_HATSU]);
+        assert!(tiles[2].is_red());
+        assert_eq!(error(parse_unicode("一萬二")).0, 6..9);
+        assert!(parse_unicode("1m").is_err());
+    }
+
+    #[test]
+    fn copies() {
+        let tokens = parse_mpsz("11110m 5m").unwrap().tiles;
+        assert!(check_copies(&tokens[..5]).is_ok());
+        assert_eq!(
+            check_copies(&parse_mpsz("5550m 5m").unwrap().tiles),
+            Err(ParseError::new(
+                6..7,
+                ParseErrorKind::CountOutOfRange {
+                    tile: T_5M,
+                    count: 5
+                }
+            ))
+        );
+    }
+}
@@ -1,62 +1,56 @@
-use crate::{full_set::FullTileSet, tile::Tile, tile_block::TileBlock, Yaku, ALL_TILES, T_INVALID};
-use anyhow::{anyhow, Error, Result};
-use once_cell::sync::Lazy;
-use regex::Regex;
-use std::{
-    collections::{BTreeMap, VecDeque},
-    fmt::Display,
-    str::FromStr,
+use crate::{
+    context::{Riichi, WinContext, WinMethod},
+    error::{Error, ParseError, ParseErrorKind, Result},
+    full_set::FullTileSet,
+    meld::Meld,
+    notation::{
+        check_copies, extra_copy, format_ankan, format_mpsz, parse_house, parse_mpsz,
+        parse_unicode, Tokens,
+    },
+    rules::Rules,
+    search::{Budget, Search, Searcher},
+    shanten::{shanten, tile_counts},
+    tile::{Tile, Wind},
+    ukeire::{ukeire, Ukeire},
+    Yaku, T_INVALID,
 };
+use std::{fmt::Display, str::FromStr};
 
-static TILESET_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"((ton|nan|shaa|pei|haku|chun|hatsu)|([1-9]+)([psm]))(\d+)?").unwrap()
-});
-
+/// 13 tiles waiting for the 14th, a kan counted as 3,
+/// scored as the south seat of the east round unless `with_winds` says otherwise
 #[derive(Debug, Clone, Copy)]
 pub struct ReadyTileSet {
+    /// concealed tiles, sorted and padded with `T_INVALID`
     pub(crate) tiles: [Tile; 14],
+    pub(crate) melds: [Option<Meld>; 4],
+    pub(crate) context: WinContext,
 }
 
 impl FromStr for ReadyTileSet {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self> {
-        let mut tiles = Vec::with_capacity(14);
-        for cap in TILESET_REGEX.captures_iter(s) {
-            let num = cap
-                .get(5)
-                .map_or_else(|| 1, |s| s.as_str().parse().unwrap());
-            let v = if let Some(s) = cap.get(2) {
-                vec![Tile::from_str(s.as_str()).unwrap()]
-            } else {
-                cap[3]
-                    .chars()
-                    .map(|ch| Tile::from_str(&format!("{}{}", ch, &cap[4])).unwrap())
-                    .collect()
-            };
-            for _ in 0..num {
-                tiles.extend_from_slice(&v);
-            }
-        }
-        if tiles.len() != 13 {
-            Err(anyhow!("wrong number of tiles: {}", tiles.len()))
+    type Err = ParseError;
+
+    /// house format such as `123p 4m3 haku [ton4]` with `[ton4]` as an ankan,
+    /// or glyphs or kanji, see `Tile::from_unicode` and `Tile::from_kanji`
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let tokens = if s.is_ascii() {
+            parse_house(s)?
         } else {
-            tiles.sort();
-            tiles.push(T_INVALID);
-            Ok(ReadyTileSet {
-                tiles: tiles.try_into().unwrap(),
-            })
-        }
+            Tokens {
+                tiles: parse_unicode(s)?,
+                kans: vec![],
+            }
+        };
+        ReadyTileSet::from_tokens(s, &tokens)
     }
 }
 
 impl Display for ReadyTileSet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let text = self
-            .tiles
+            .concealed()
             .iter()
-            .take(13)
             .map(|tile| tile.to_string())
+            .chain(self.melds().map(|meld| meld.to_string()))
             .collect::<Vec<_>>()
             .join(" ");
         write!(f, "{text}")
@@ -64,312 +58,215 @@ impl Display for ReadyTileSet {
 }
 
 impl ReadyTileSet {
-    /// a very heavy search for all possible situation
-    pub fn check(&self) -> (u8, Vec<(Tile, Vec<Yaku>)>) {
-        // check tenpai
-        let tenpai_ret = ALL_TILES
-            .into_iter()
-            .filter_map(|draw_tile| self.draw(draw_tile).yakus().map(|yakus| (draw_tile, yakus)))
-            .collect::<Vec<_>>();
-        if !tenpai_ret.is_empty() {
-            return (0, tenpai_ret);
+    /// concealed tiles together with called melds, a kan takes the place of 3 tiles
+    pub fn new(tiles: &[Tile], melds: &[Meld]) -> Result<Self> {
+        if melds.len() > 4 {
+            return Err(Error::TooManyMelds(melds.len()));
         }
-
-        // check kokushi shanten
-        let mut yaochuus = self.tiles[..13]
+        if tiles.len() + 3 * melds.len() != 13 {
+            return Err(Error::WrongTileCount(tiles.len() + 3 * melds.len()));
+        }
+        let all = tiles
             .iter()
-            .filter(|&&tile| tile.is_terminal() || tile.is_honor())
-            .cloned()
+            .chain(melds.iter().flat_map(|meld| meld.tiles()))
+            .copied()
             .collect::<Vec<_>>();
-        let extras = yaochuus
-            .windows(2)
-            .filter_map(|tiles| {
-                if tiles[0] == tiles[1] {
-                    Some(tiles[0])
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
-        yaochuus.dedup();
-        let distinct_yaochuu_num = yaochuus.len() as u8;
-
-        // all three kind
-        let kokushi_shanten_num = 13 - (distinct_yaochuu_num + extras.is_empty() as u8);
-        let common_shanten_num = self.common_shanten();
-        let chiitoi_shanten_num = self.chiitou_shanten();
-
-        let shanten_num = kokushi_shanten_num
-            .min(common_shanten_num)
-            .min(chiitoi_shanten_num);
-        let mut shanten_ret = vec![];
-        if shanten_num > 2 {
-            return (shanten_num, shanten_ret);
-        }
-
-        // kokushi
-        if kokushi_shanten_num == shanten_num {
-            let all_yaochuus = ALL_TILES
-                .into_iter()
-                .filter(|tile| tile.is_honor() || tile.is_terminal())
-                .collect::<Vec<_>>();
-            shanten_ret = if extras.is_empty() {
-                all_yaochuus
-                    .iter()
-                    .map(|&tile| {
-                        if yaochuus.iter().any(|&t| t == tile) {
-                            (tile, vec![Yaku::Kokushimusou])
-                        } else {
-                            (tile, vec![Yaku::Kokushimusou13])
-                        }
-                    })
-                    .collect::<Vec<_>>()
+        if let Some((index, count)) = extra_copy(all.iter().copied()) {
+            return Err(if count > 4 {
+                Error::TooManyCopies(all[index])
             } else {
-                all_yaochuus
-                    .iter()
-                    .filter(|&&tile| !yaochuus.iter().any(|&t| t == tile))
-                    .map(|&tile| (tile, vec![Yaku::Kokushimusou]))
-                    .collect()
-            };
+                Error::ExtraRedFive(all[index])
+            });
         }
-
-        // chiitoi
-        if chiitoi_shanten_num == shanten_num {
-            let mut search_queue = VecDeque::new();
-            search_queue.push_back((chiitoi_shanten_num, T_INVALID, *self));
-            while let Some((cur_shanten_num, first_income, ready_set)) = search_queue.pop_front() {
-                for draw_tile in ALL_TILES
-                    .into_iter()
-                    .filter(|&tile| ready_set.maybe_effective(tile))
-                {
-                    let full_set = ready_set.draw(draw_tile);
-                    let yakus = full_set.yakus();
-
-                    if let Some(yakus) = yakus {
-                        shanten_ret.push((first_income, yakus));
-                    } else if cur_shanten_num != 0 {
-                        for discard_tile in
-                            full_set.tiles.into_iter().filter(|&tile| tile != draw_tile)
-                        {
-                            let next_ready_set = full_set.discard(discard_tile).unwrap();
-                            let next_shanten_num = next_ready_set.chiitou_shanten();
-                            if next_shanten_num < cur_shanten_num {
-                                search_queue.push_back((
-                                    next_shanten_num,
-                                    if first_income == T_INVALID {
-                                        draw_tile
-                                    } else {
-                                        first_income
-                                    },
-                                    next_ready_set,
-                                ));
-                            }
-                        }
-                    }
-                }
-            }
+        let mut padded = [T_INVALID; 14];
+        padded[..tiles.len()].copy_from_slice(tiles);
+        padded.sort();
+        let mut meld_slots = [None; 4];
+        for (slot, &meld) in meld_slots.iter_mut().zip(melds) {
+            *slot = Some(meld);
         }
+        Ok(ReadyTileSet {
+            tiles: padded,
+            melds: meld_slots,
+            context: WinContext::default(),
+        })
+    }
 
-        // common
-        if common_shanten_num == shanten_num {
-            let mut search_queue = VecDeque::new();
-            search_queue.push_back((common_shanten_num, T_INVALID, *self));
-            while let Some((cur_shanten_num, first_income, ready_set)) = search_queue.pop_front() {
-                for draw_tile in ALL_TILES
-                    .into_iter()
-                    .filter(|&tile| ready_set.maybe_effective(tile))
-                {
-                    let full_set = ready_set.draw(draw_tile);
-                    let yakus = full_set.yakus();
-
-                    if let Some(yakus) = yakus {
-                        shanten_ret.push((first_income, yakus));
-                    } else if cur_shanten_num != 0 {
-                        for discard_tile in
-                            full_set.tiles.into_iter().filter(|&tile| tile != draw_tile)
-                        {
-                            let next_ready_set = full_set.discard(discard_tile).unwrap();
-                            let next_shanten_num = next_ready_set.common_shanten();
-                            if next_shanten_num < cur_shanten_num {
-                                search_queue.push_back((
-                                    next_shanten_num,
-                                    if first_income == T_INVALID {
-                                        draw_tile
-                                    } else {
-                                        first_income
-                                    },
-                                    next_ready_set,
-                                ));
-                            }
-                        }
-                    }
-                }
-            }
+    /// concealed tiles in mpsz such as `123m406p789s1122z`, ankan as `[1111z]`
+    pub fn from_mpsz(s: &str) -> Result<Self, ParseError> {
+        ReadyTileSet::from_tokens(s, &parse_mpsz(s)?)
+    }
+
+    /// 13 parsed tiles and one more for each ankan, at most 4 copies of each
+    pub(crate) fn from_tokens(s: &str, tokens: &Tokens) -> Result<Self, ParseError> {
+        check_copies(&tokens.all())?;
+        if tokens.tiles.len() + 3 * tokens.kans.len() != 13 {
+            return Err(ParseError::new(
+                0..s.len(),
+                ParseErrorKind::WrongHandSize {
+                    expected: 13 + tokens.kans.len(),
+                    found: tokens.len(),
+                },
+            ));
         }
+        let tiles = tokens
+            .tiles
+            .iter()
+            .map(|(tile, _)| *tile)
+            .collect::<Vec<_>>();
+        let kans = tokens
+            .kans
+            .iter()
+            .map(|kan| {
+                // a red five goes first in the kan
+                let (tile, _) = kan
+                    .iter()
+                    .find(|(tile, _)| tile.is_red())
+                    .unwrap_or(&kan[0]);
+                Meld::ankan(*tile)
+            })
+            .collect::<Result<Vec<_>>>()
+            .map_err(|error| ParseError::invalid_hand(s, error))?;
+        ReadyTileSet::new(&tiles, &kans).map_err(|error| ParseError::invalid_hand(s, error))
+    }
 
-        shanten_ret.sort();
-        shanten_ret.dedup();
-        (shanten_num, shanten_ret)
+    /// concealed tiles in mpsz with ankan in brackets, other melds are not written
+    pub fn to_mpsz(&self) -> String {
+        format_mpsz(self.concealed()) + &format_ankan(self.melds())
     }
 
-    pub fn draw(self, tile: Tile) -> FullTileSet {
-        let mut tiles = self.tiles;
-        let res = tiles.binary_search(&tile);
-        let index = match res {
-            Ok(i) => i,
-            Err(i) => i,
-        }
-        .min(tiles.len() - 1);
-        tiles[index..].rotate_right(1);
-        tiles[index] = tile;
-        FullTileSet {
-            tiles,
-            last_draw: tile,
+    /// concealed tiles as glyphs, melds are not written
+    pub fn to_unicode(&self) -> String {
+        self.concealed()
+            .iter()
+            .map(|tile| tile.to_unicode())
+            .collect()
+    }
+
+    /// concealed tiles in kanji, melds are not written
+    pub fn to_kanji(&self) -> String {
+        self.concealed()
+            .iter()
+            .map(|tile| tile.to_kanji())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// closed hand only, `ippatsu` means 一发 is still live
+    pub fn with_riichi(self, riichi: Riichi, ippatsu: bool) -> Result<Self> {
+        if !self.is_closed() {
+            return Err(Error::OpenRiichi);
         }
+        Ok(ReadyTileSet {
+            context: self.context.with_riichi(riichi, ippatsu),
+            ..self
+        })
     }
 
-    /// calculate shanten num and tiles that could forward shanten in chiitoi pattern
-    fn chiitou_shanten(&self) -> u8 {
-        let mut pair_tile = vec![];
-        let mut index = 0;
-        while index < 12 {
-            if self.tiles[index] == self.tiles[index + 1] {
-                if let Some(&last) = pair_tile.last() {
-                    if last != self.tiles[index] {
-                        pair_tile.push(self.tiles[index]);
-                    }
-                } else {
-                    pair_tile.push(self.tiles[index]);
-                }
-                index += 2;
-            } else {
-                index += 1;
-            }
+    /// east round and south seat if not set, the dealer sits east
+    pub fn with_winds(self, round_wind: Wind, seat_wind: Wind) -> Self {
+        ReadyTileSet {
+            context: self.context.with_winds(round_wind, seat_wind),
+            ..self
         }
-        6 - pair_tile.len() as u8
     }
 
-    /// calculate shanten num and tiles that could forward shanten in common pattern
-    fn common_shanten(&self) -> u8 {
-        let mut tile_left = BTreeMap::new();
-        for &tile in &self.tiles[..13] {
-            *tile_left.entry(tile).or_default() += 1;
+    pub fn with_rules(self, rules: Rules) -> Self {
+        ReadyTileSet {
+            context: self.context.with_rules(rules),
+            ..self
         }
+    }
 
-        let mut min_shanten = 8;
-        Self::find_common_patterns(&mut tile_left, 0, 0, false, &mut min_shanten);
-        min_shanten
+    pub fn concealed(&self) -> &[Tile] {
+        &self.tiles[..13 - 3 * self.melds().count()]
     }
 
-    fn find_common_patterns(
-        tile_left: &mut BTreeMap<Tile, u8>,
-        completed: u8,
-        incompleted: u8,
-        have_pair: bool,
-        min_shanten: &mut u8,
-    ) {
-        if completed + incompleted - have_pair as u8 > 4 {
-            return;
-        }
+    pub fn melds(&self) -> impl Iterator<Item = &Meld> {
+        self.melds.iter().flatten()
+    }
 
-        let kvs = tile_left
-            .iter()
-            .filter_map(|(&k, &v)| if v > 0 { Some((k, v)) } else { None })
-            .collect::<Vec<_>>();
+    /// ankan keeps the hand closed
+    pub fn is_closed(&self) -> bool {
+        self.melds().all(|meld| !meld.is_open())
+    }
 
-        let current_shanten = 8 - 2 * completed - incompleted;
-        *min_shanten = (*min_shanten).min(current_shanten);
-        if kvs.is_empty() {
-            return;
-        }
+    /// effective tiles with their unseen copies, `visible` counts this hand,
+    /// its melds, all rivers and the dora indicators as in `tile_counts`
+    pub fn ukeire(&self, visible: &[u8; 34]) -> Ukeire {
+        ukeire(
+            &tile_counts(self.concealed()),
+            self.melds().count() as u8,
+            visible,
+        )
+    }
 
-        let tile_left_num = kvs.iter().map(|(_, v)| *v).sum::<u8>();
-        if tile_left_num / 3 * 2 + (tile_left_num % 3) / 2 < current_shanten - *min_shanten {
-            return;
-        }
+    /// least shanten of all forms, kokushi and chiitoi only without melds
+    pub fn shanten(&self) -> i8 {
+        shanten(&tile_counts(self.concealed()), self.melds().count() as u8)
+    }
 
-        // continue with a triplet
-        if *tile_left.get(&kvs[0].0).unwrap() >= 3 {
-            *tile_left.get_mut(&kvs[0].0).unwrap() -= 3;
-            Self::find_common_patterns(
-                tile_left,
-                completed + 1,
-                incompleted,
-                have_pair,
-                min_shanten,
-            );
-            *tile_left.get_mut(&kvs[0].0).unwrap() += 3;
-        }
+    /// draws that lower the shanten
+    pub fn effective_draws(&self) -> Vec<Tile> {
+        self.ukeire(&tile_counts(self.concealed()))
+            .tiles
+            .into_iter()
+            .map(|(tile, _)| tile)
+            .collect()
+    }
+
+    /// `search` with the default budget, without telling if it ran out
+    pub fn check(&self) -> (i8, Vec<(Tile, Vec<Yaku>)>) {
+        let search = self.search(Budget::default());
+        (search.shanten, search.wins)
+    }
 
-        // continue with a sequence
-        if kvs.len() >= 3 {
-            if let Ok(current) = TileBlock::new_sequence([kvs[0].0, kvs[1].0, kvs[2].0]) {
-                *tile_left.get_mut(&current.tiles()[0]).unwrap() -= 1;
-                *tile_left.get_mut(&current.tiles()[1]).unwrap() -= 1;
-                *tile_left.get_mut(&current.tiles()[2]).unwrap() -= 1;
-                Self::find_common_patterns(
-                    tile_left,
-                    completed + 1,
-                    incompleted,
-                    have_pair,
-                    min_shanten,
-                );
-                *tile_left.get_mut(&current.tiles()[0]).unwrap() += 1;
-                *tile_left.get_mut(&current.tiles()[1]).unwrap() += 1;
-                *tile_left.get_mut(&current.tiles()[2]).unwrap() += 1;
+    /// every effective draw and the yaku of wins reachable from it,
+    /// only following draws and discards that lower the shanten
+    pub fn search(&self, budget: Budget) -> Search {
+        let shanten = self.shanten();
+        let draws = self.effective_draws();
+        let mut searcher = Searcher::new(budget);
+        let mut wins = vec![];
+        for &draw in &draws {
+            for yakus in searcher.after_draw(self, shanten, draw) {
+                wins.push((draw, yakus));
             }
         }
-
-        // continue with a pair
-        if *tile_left.get(&kvs[0].0).unwrap() >= 2 {
-            *tile_left.get_mut(&kvs[0].0).unwrap() -= 2;
-            Self::find_common_patterns(tile_left, completed, incompleted + 1, true, min_shanten);
-            *tile_left.get_mut(&kvs[0].0).unwrap() += 2;
+        Search {
+            shanten,
+            draws,
+            wins,
+            exhausted: searcher.exhausted(),
         }
+    }
 
-        // continue with a incompleted AB
-        if kvs.len() >= 2 {
-            if let Ok(current) = TileBlock::new_incompleted([kvs[0].0, kvs[1].0]) {
-                *tile_left.get_mut(&current.tiles()[0]).unwrap() -= 1;
-                *tile_left.get_mut(&current.tiles()[1]).unwrap() -= 1;
-                Self::find_common_patterns(
-                    tile_left,
-                    completed,
-                    incompleted + 1,
-                    have_pair,
-                    min_shanten,
-                );
-                *tile_left.get_mut(&current.tiles()[0]).unwrap() += 1;
-                *tile_left.get_mut(&current.tiles()[1]).unwrap() += 1;
-            }
+    pub fn draw(self, tile: Tile) -> FullTileSet {
+        let mut tiles = self.tiles;
+        let res = tiles.binary_search(&tile);
+        let index = match res {
+            Ok(i) => i,
+            Err(i) => i,
         }
-
-        // continue with a incompleted AC
-        if kvs.len() >= 3 {
-            if let Ok(current) = TileBlock::new_incompleted([kvs[0].0, kvs[2].0]) {
-                *tile_left.get_mut(&current.tiles()[0]).unwrap() -= 1;
-                *tile_left.get_mut(&current.tiles()[1]).unwrap() -= 1;
-                Self::find_common_patterns(
-                    tile_left,
-                    completed,
-                    incompleted + 1,
-                    have_pair,
-                    min_shanten,
-                );
-                *tile_left.get_mut(&current.tiles()[0]).unwrap() += 1;
-                *tile_left.get_mut(&current.tiles()[1]).unwrap() += 1;
-            }
+        .min(tiles.len() - 1);
+        tiles[index..].rotate_right(1);
+        tiles[index] = tile;
+        FullTileSet {
+            tiles,
+            melds: self.melds,
+            last_draw: tile,
+            context: WinContext {
+                win_method: WinMethod::Tsumo,
+                ..self.context
+            },
         }
-
-        // continue without this tile
-        *tile_left.get_mut(&kvs[0].0).unwrap() -= 1;
-        Self::find_common_patterns(tile_left, completed, incompleted, have_pair, min_shanten);
-        *tile_left.get_mut(&kvs[0].0).unwrap() += 1;
     }
 
-    /// maybe forward shanten
-    fn maybe_effective(&self, draw: Tile) -> bool {
-        self.tiles[..13].iter().any(|&tile| draw.is_related(tile))
+    /// win on the tile discarded by others
+    pub fn ron(self, tile: Tile) -> FullTileSet {
+        let mut full_set = self.draw(tile);
+        full_set.context.win_method = WinMethod::Ron;
+        full_set
     }
 }
 
@@ -398,11 +295,122 @@ mod tests {
             "1p 1p 1p 2p 3p 4p 5p 6p 7p 8p 9p 9p 9p"
         );
 
+        let error = ReadyTileSet::from_str("hatsu12 haku0 chun").unwrap_err();
+        assert_eq!(error.span, 0..7);
+        assert_eq!(
+            error.to_string(),
+            "12 copies of hatsu, expected 1 to 4 at 0..7"
+        );
+        let error = ReadyTileSet::from_str("hatsu3 hatsu2 1p4 2p4").unwrap_err();
+        assert_eq!(error.span, 7..13);
+        let error =
+            ReadyTileSet::from_str("19p 19s 19m haku hatsu chun tonn nan shaa").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::UnknownTile("tonn".to_string()));
+        assert_eq!(error.span, 28..32);
         assert_eq!(
-            ReadyTileSet::from_str("hatsu12 haku0 chun")
+            ReadyTileSet::from_str("19p 19s 19m haku hatsu chun").unwrap_err(),
+            ParseError::new(
+                0..27,
+                ParseErrorKind::WrongHandSize {
+                    expected: 13,
+                    found: 9
+                }
+            )
+        );
+        assert_eq!(
+            ReadyTileSet::from_str("1234067p 0m 5s2 0s 5m2")
                 .unwrap()
                 .to_string(),
-            "hatsu hatsu hatsu hatsu hatsu hatsu hatsu hatsu hatsu hatsu hatsu hatsu chun"
+            "0m 5m 5m 1p 2p 3p 4p 0p 6p 7p 5s 5s 0s"
+        );
+    }
+
+    #[test]
+    fn kan() {
+        use crate::*;
+
+        let tileset = ReadyTileSet::from_str("123p 456m 789s haku [ton4]").unwrap();
+        assert_eq!(tileset.concealed().len(), 10);
+        assert_eq!(tileset.melds().next(), Some(&Meld::ankan(T_TON).unwrap()));
+        assert!(tileset.is_closed());
+        assert_eq!(tileset.to_mpsz(), "456m123p789s5z[1111z]");
+        assert_eq!(
+            ReadyTileSet::from_mpsz(&tileset.to_mpsz()).unwrap().melds,
+            tileset.melds
+        );
+
+        let tileset = ReadyTileSet::from_mpsz("[0555m][1111p][9999s][7777z]1z").unwrap();
+        assert_eq!(tileset.melds().count(), 4);
+        assert_eq!(
+            tileset.to_string(),
+            "ton [0m 5m 5m 5m] [1p 1p 1p 1p] [9s 9s 9s 9s] [chun chun chun chun]"
+        );
+        for s in ["1111m 234m 567p 789s", "1m4 234m 567p 789s"] {
+            let tileset = ReadyTileSet::from_str(s).unwrap();
+            assert_eq!(tileset.concealed().len(), 13);
+            assert_eq!(tileset.melds().count(), 0);
+        }
+        assert_eq!(
+            ReadyTileSet::from_str("[1111p] 123m").unwrap_err().kind,
+            ParseErrorKind::WrongHandSize {
+                expected: 14,
+                found: 7
+            }
+        );
+    }
+
+    #[test]
+    fn unicode_and_kanji() {
+        let tileset = ReadyTileSet::from_str("123p 406m 789s 1s2 haku2").unwrap();
+        assert_eq!(tileset.to_unicode(), "🀊🀋🀌🀙🀚🀛🀐🀐🀖🀗🀘🀆🀆");
+        assert_eq!(
+            tileset.to_kanji(),
+            "四萬 赤五萬 六萬 ①筒 ②筒 ③筒 一索 一索 七索 八索 九索 白 白"
+        );
+        let from_kanji = ReadyTileSet::from_str(&tileset.to_kanji()).unwrap();
+        assert_eq!(from_kanji.to_string(), tileset.to_string());
+        let from_unicode = ReadyTileSet::from_str(&tileset.to_unicode()).unwrap();
+        assert_eq!(from_unicode.concealed(), tileset.concealed());
+        assert!(ReadyTileSet::from_str("🀇🀈🀉").is_err());
+    }
+
+    #[test]
+    fn open_hand() {
+        use crate::*;
+
+        let chi = Meld::chi([T_7S, T_8S, T_9S]).unwrap();
+        let tiles = [T_2P, T_3P, T_4P, T_5M, T_6M, T_7M, T_3S, T_4S, T_5S, T_3P];
+        let tileset = ReadyTileSet::new(&tiles, &[chi]).unwrap();
+        assert!(!tileset.is_closed());
+        assert_eq!(
+            tileset.to_string(),
+            "5m 6m 7m 2p 3p 3p 4p 3s 4s 5s [7s 8s 9s]"
+        );
+        assert_eq!(tileset.check(), (0, vec![(T_3P, vec![])]));
+
+        // two 3p in the hand, one more in a river
+        let mut visible = tile_counts(&tiles);
+        visible[T_3P.index()] += 1;
+        let ukeire = tileset.ukeire(&visible);
+        assert_eq!(ukeire.tiles, vec![(T_3P, 1)]);
+        assert_eq!(ukeire.total, 1);
+
+        assert_eq!(
+            ReadyTileSet::new(&tiles, &[]).unwrap_err(),
+            Error::WrongTileCount(10)
+        );
+        assert_eq!(
+            ReadyTileSet::new(&tiles, &[chi; 5]).unwrap_err(),
+            Error::TooManyMelds(5)
+        );
+        assert!(ReadyTileSet::new(&tiles, &[chi, chi]).is_err());
+        assert_eq!(
+            ReadyTileSet::new(&[T_1M; 13], &[]).unwrap_err(),
+            Error::TooManyCopies(T_1M)
+        );
+        assert_eq!(
+            ReadyTileSet::new(&tiles, &[Meld::ankan(T_3P).unwrap()]).unwrap_err(),
+            Error::TooManyCopies(T_3P)
         );
     }
 }
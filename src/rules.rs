@@ -0,0 +1,251 @@
+use crate::error::{Error, Result};
+use std::str::FromStr;
+
+/// how 人和 is scored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RenhouRule {
+    /// not a yaku
+    #[default]
+    Disabled,
+    /// scored as mangan, other yaku and dora don't count
+    Mangan,
+    /// 役满
+    Yakuman,
+}
+
+/// fu of a pair of the seat wind that is also the round wind
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DoubleWindPairFu {
+    /// same as any other yakuhai pair
+    Two,
+    /// 2 fu for each wind
+    #[default]
+    Four,
+}
+
+impl DoubleWindPairFu {
+    pub fn fu(&self) -> u8 {
+        match self {
+            DoubleWindPairFu::Two => 2,
+            DoubleWindPairFu::Four => 4,
+        }
+    }
+}
+
+/// optional rules that differ between rule sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Rules {
+    /// 食断, tanyao with an open hand
+    pub kuitan: bool,
+    /// 双倍役满 for suuankou tanki, 13-sided kokushi, junsei chuuren and daisuushii
+    pub double_yakuman: bool,
+    /// 累计役满, 13 han or more is a yakuman instead of a sanbaiman
+    pub kazoe_yakuman: bool,
+    /// 切上满贯, 4 han 30 fu and 3 han 60 fu are mangan
+    pub kiriage_mangan: bool,
+    /// 连风牌雀头
+    pub double_wind_pair_fu: DoubleWindPairFu,
+    /// 人和, ron before the first draw
+    pub renhou: RenhouRule,
+    /// pinfu is allowed with tsumo, otherwise tsumo takes its 2 fu
+    pub pinfu_tsumo: bool,
+    /// 一发
+    pub ippatsu: bool,
+    /// 里宝牌
+    pub ura_dora: bool,
+    /// 赤宝牌
+    pub aka_dora: bool,
+}
+
+/// same as Mahjong Soul ranked
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            kuitan: true,
+            double_yakuman: true,
+            kazoe_yakuman: true,
+            kiriage_mangan: false,
+            double_wind_pair_fu: DoubleWindPairFu::Four,
+            renhou: RenhouRule::Disabled,
+            pinfu_tsumo: true,
+            ippatsu: true,
+            ura_dora: true,
+            aka_dora: true,
+        }
+    }
+}
+
+impl Rules {
+    /// names accepted by `from_str`
+    pub const PRESETS: [&'static str; 6] =
+        ["tenhou", "mahjongsoul", "wrc", "ema", "jpml-a", "jpml-b"];
+
+    /// 天凤, no double yakuman
+    pub fn tenhou() -> Self {
+        Rules {
+            double_yakuman: false,
+            ..Default::default()
+        }
+    }
+
+    /// 雀魂 ranked
+    pub fn mahjong_soul() -> Self {
+        Rules::default()
+    }
+
+    /// World Riichi Championship
+    pub fn wrc() -> Self {
+        Rules {
+            double_yakuman: false,
+            kazoe_yakuman: false,
+            kiriage_mangan: true,
+            double_wind_pair_fu: DoubleWindPairFu::Two,
+            aka_dora: false,
+            ..Default::default()
+        }
+    }
+
+    /// European Mahjong Association
+    pub fn ema() -> Self {
+        Rules {
+            double_yakuman: false,
+            kazoe_yakuman: false,
+            renhou: RenhouRule::Mangan,
+            aka_dora: false,
+            ..Default::default()
+        }
+    }
+
+    /// 日本职业麻将联盟 A rules, no ippatsu, ura or red fives
+    pub fn jpml_a() -> Self {
+        Rules {
+            double_yakuman: false,
+            kazoe_yakuman: false,
+            double_wind_pair_fu: DoubleWindPairFu::Two,
+            ippatsu: false,
+            ura_dora: false,
+            aka_dora: false,
+            ..Default::default()
+        }
+    }
+
+    /// 日本职业麻将联盟 B rules, A rules with ippatsu and ura
+    pub fn jpml_b() -> Self {
+        Rules {
+            ippatsu: true,
+            ura_dora: true,
+            ..Rules::jpml_a()
+        }
+    }
+}
+
+impl FromStr for Rules {
+    type Err = Error;
+
+    /// preset by name, case insensitive
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "tenhou" => Ok(Rules::tenhou()),
+            "mahjongsoul" | "majsoul" => Ok(Rules::mahjong_soul()),
+            "wrc" => Ok(Rules::wrc()),
+            "ema" => Ok(Rules::ema()),
+            "jpml-a" => Ok(Rules::jpml_a()),
+            "jpml-b" => Ok(Rules::jpml_b()),
+            _ => Err(Error::UnknownPreset(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    /// non-dealer unless said otherwise, total points or 0 without yaku
+    fn golden_hands(rules: Rules) -> [u32; 6] {
+        let total = |tileset: FullTileSet| tileset.points().map_or(0, |points| points.total());
+        let hand = |tiles: &str| {
+            ReadyTileSet::from_str(tiles)
+                .unwrap()
+                .with_winds(Wind::Ton, Wind::Nan)
+                .with_rules(rules)
+        };
+        [
+            // suuankou tanki, double yakuman or not
+            total(hand("1m3 5p3 9s3 shaa3 chun").ron(T_CHUN)),
+            // riichi pinfu tanyao, a red five and a dora, 4 han 30 fu without the red five
+            total(
+                hand("23p 406m 345s 678s 5p2")
+                    .with_riichi(Riichi::Riichi, false)
+                    .unwrap()
+                    .ron(T_4P)
+                    .with_dora(&[T_2S], &[])
+                    .unwrap(),
+            ),
+            // riichi ippatsu pinfu tanyao and an ura dora
+            total(
+                hand("23p 456m 345s 678s 5p2")
+                    .with_riichi(Riichi::Riichi, true)
+                    .unwrap()
+                    .ron(T_4P)
+                    .with_dora(&[], &[T_4M])
+                    .unwrap(),
+            ),
+            // riichi tsumo pinfu iipeikou chiniisou and 4 dora, 14 han
+            total(
+                hand("112233p 456p 78p 9p2")
+                    .with_riichi(Riichi::Riichi, false)
+                    .unwrap()
+                    .draw(T_6P)
+                    .with_dora(&[T_8P, T_5P], &[])
+                    .unwrap(),
+            ),
+            // riichi by the dealer with a double east pair, 40 or 50 fu
+            total(
+                hand("234p 1m3 345s 78s ton2")
+                    .with_winds(Wind::Ton, Wind::Ton)
+                    .with_riichi(Riichi::Riichi, false)
+                    .unwrap()
+                    .ron(T_9S),
+            ),
+            // renhou without any other yaku
+            total(
+                hand("234p 567m 345s 789s 3p")
+                    .ron(T_3P)
+                    .with_situation(WinSituation {
+                        first_draw: true,
+                        ..Default::default()
+                    })
+                    .unwrap(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn presets() {
+        let golden = [
+            ("tenhou", [32000, 8000, 8000, 32000, 2400, 0]),
+            ("mahjongsoul", [64000, 8000, 8000, 32000, 2400, 0]),
+            ("wrc", [32000, 8000, 8000, 24000, 2000, 0]),
+            ("ema", [32000, 7700, 8000, 24000, 2400, 8000]),
+            ("jpml-a", [32000, 7700, 3900, 24000, 2000, 0]),
+            ("jpml-b", [32000, 7700, 8000, 24000, 2000, 0]),
+        ];
+        for (name, points) in golden {
+            assert_eq!(golden_hands(name.parse().unwrap()), points, "{name}");
+        }
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Rules::from_str("JPML-A").unwrap(), Rules::jpml_a());
+        assert_eq!(Rules::from_str("majsoul").unwrap(), Rules::default());
+        assert_eq!(
+            Rules::from_str("m-league"),
+            Err(Error::UnknownPreset("m-league".to_string()))
+        );
+        for name in Rules::PRESETS {
+            assert!(Rules::from_str(name).is_ok());
+        }
+    }
+}
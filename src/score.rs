@@ -0,0 +1,280 @@
+use crate::{context::WinMethod, rules::Rules, yaku::Han};
+use std::fmt::{Debug, Display};
+
+/// 满贯 and above
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Limit {
+    /// 满贯
+    Mangan,
+    /// 跳满
+    Haneman,
+    /// 倍满
+    Baiman,
+    /// 三倍满
+    Sanbaiman,
+    /// 役满, counted or not, with its multiple
+    Yakuman(u8),
+}
+
+impl Limit {
+    /// 基本点 of the limit
+    pub fn basic_points(&self) -> u32 {
+        match self {
+            Limit::Mangan => 2000,
+            Limit::Haneman => 3000,
+            Limit::Baiman => 4000,
+            Limit::Sanbaiman => 6000,
+            Limit::Yakuman(multiple) => 8000 * *multiple as u32,
+        }
+    }
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Limit::Yakuman(1) => write!(f, "Yakuman"),
+            Limit::Yakuman(multiple) => write!(f, "Yakuman x{multiple}"),
+            limit => <Self as Debug>::fmt(limit, f),
+        }
+    }
+}
+
+/// what the winner gets from others, honba included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Payment {
+    /// paid by the discarder
+    Ron(u32),
+    /// paid by each of the other three, when the dealer tsumo
+    DealerTsumo(u32),
+    /// paid by the dealer and by each of the other two non-dealers
+    Tsumo { dealer: u32, non_dealer: u32 },
+}
+
+impl Payment {
+    pub fn total(&self) -> u32 {
+        match *self {
+            Payment::Ron(points) => points,
+            Payment::DealerTsumo(points) => points * 3,
+            Payment::Tsumo { dealer, non_dealer } => dealer + non_dealer * 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Points {
+    /// 基本点
+    pub basic_points: u32,
+    pub limit: Option<Limit>,
+    pub payment: Payment,
+    /// riichi sticks on the table, 1000 each
+    pub deposit: u32,
+}
+
+impl Points {
+    pub fn new(
+        han: Han,
+        fu: u8,
+        is_dealer: bool,
+        win_method: WinMethod,
+        honba: u8,
+        riichi_sticks: u8,
+        rules: &Rules,
+    ) -> Self {
+        let (basic_points, limit) = Self::basic_points(han, fu, rules);
+        Self::from_basic_points(
+            basic_points,
+            limit,
+            is_dealer,
+            win_method,
+            honba,
+            riichi_sticks,
+        )
+    }
+
+    /// points of a limit reached without counting han, such as mangan renhou
+    pub(crate) fn limited(
+        limit: Limit,
+        is_dealer: bool,
+        win_method: WinMethod,
+        honba: u8,
+        riichi_sticks: u8,
+    ) -> Self {
+        Self::from_basic_points(
+            limit.basic_points(),
+            Some(limit),
+            is_dealer,
+            win_method,
+            honba,
+            riichi_sticks,
+        )
+    }
+
+    fn from_basic_points(
+        basic_points: u32,
+        limit: Option<Limit>,
+        is_dealer: bool,
+        win_method: WinMethod,
+        honba: u8,
+        riichi_sticks: u8,
+    ) -> Self {
+        let honba = honba as u32;
+        let payment = match (win_method, is_dealer) {
+            (WinMethod::Ron, true) => Payment::Ron(round_up(basic_points * 6) + 300 * honba),
+            (WinMethod::Ron, false) => Payment::Ron(round_up(basic_points * 4) + 300 * honba),
+            (WinMethod::Tsumo, true) => {
+                Payment::DealerTsumo(round_up(basic_points * 2) + 100 * honba)
+            }
+            (WinMethod::Tsumo, false) => Payment::Tsumo {
+                dealer: round_up(basic_points * 2) + 100 * honba,
+                non_dealer: round_up(basic_points) + 100 * honba,
+            },
+        };
+        Points {
+            basic_points,
+            limit,
+            payment,
+            deposit: riichi_sticks as u32 * 1000,
+        }
+    }
+
+    /// everything the winner gets
+    pub fn total(&self) -> u32 {
+        self.payment.total() + self.deposit
+    }
+
+    fn basic_points(han: Han, fu: u8, rules: &Rules) -> (u32, Option<Limit>) {
+        let limit = if han.is_yakuman() {
+            Limit::Yakuman(han.value())
+        } else {
+            match han.value() {
+                13.. if rules.kazoe_yakuman => Limit::Yakuman(1),
+                11.. => Limit::Sanbaiman,
+                8..=10 => Limit::Baiman,
+                6..=7 => Limit::Haneman,
+                han => {
+                    let basic_points = fu as u32 * 2u32.pow(2 + han as u32);
+                    if basic_points >= 2000 || (rules.kiriage_mangan && basic_points >= 1920) {
+                        Limit::Mangan
+                    } else {
+                        return (basic_points, None);
+                    }
+                }
+            }
+        };
+        (limit.basic_points(), Some(limit))
+    }
+}
+
+/// round up to 100
+fn round_up(points: u32) -> u32 {
+    points.div_ceil(100) * 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_points() {
+        let rules = Rules::default();
+        assert_eq!(Points::basic_points(Han::new(1), 30, &rules), (240, None));
+        assert_eq!(Points::basic_points(Han::new(3), 60, &rules), (1920, None));
+        assert_eq!(
+            Points::basic_points(Han::new(3), 70, &rules),
+            (2000, Some(Limit::Mangan))
+        );
+        assert_eq!(
+            Points::basic_points(Han::new(5), 30, &rules),
+            (2000, Some(Limit::Mangan))
+        );
+        assert_eq!(
+            Points::basic_points(Han::new(7), 30, &rules),
+            (3000, Some(Limit::Haneman))
+        );
+        assert_eq!(
+            Points::basic_points(Han::new(10), 30, &rules),
+            (4000, Some(Limit::Baiman))
+        );
+        assert_eq!(
+            Points::basic_points(Han::new(12), 30, &rules),
+            (6000, Some(Limit::Sanbaiman))
+        );
+        assert_eq!(
+            Points::basic_points(Han::new(13), 30, &rules),
+            (8000, Some(Limit::Yakuman(1)))
+        );
+        assert_eq!(
+            Points::basic_points(Han::double_yakuman(), 30, &rules),
+            (16000, Some(Limit::Yakuman(2)))
+        );
+        assert_eq!(Limit::Haneman.to_string(), "Haneman");
+        assert_eq!(Limit::Yakuman(1).to_string(), "Yakuman");
+        assert_eq!(Limit::Yakuman(2).to_string(), "Yakuman x2");
+    }
+
+    #[test]
+    fn optional_limits() {
+        let rules = Rules {
+            kazoe_yakuman: false,
+            kiriage_mangan: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Points::basic_points(Han::new(13), 30, &rules),
+            (6000, Some(Limit::Sanbaiman))
+        );
+        assert_eq!(
+            Points::basic_points(Han::new(4), 30, &rules),
+            (2000, Some(Limit::Mangan))
+        );
+        assert_eq!(
+            Points::basic_points(Han::new(3), 60, &rules),
+            (2000, Some(Limit::Mangan))
+        );
+        assert_eq!(Points::basic_points(Han::new(3), 50, &rules), (1600, None));
+        assert_eq!(
+            Points::basic_points(Han::yakuman(), 30, &rules),
+            (8000, Some(Limit::Yakuman(1)))
+        );
+    }
+
+    #[test]
+    fn payment() {
+        let rules = Rules::default();
+        let points = Points::new(Han::new(1), 30, false, WinMethod::Ron, 0, 0, &rules);
+        assert_eq!(points.payment, Payment::Ron(1000));
+        let points = Points::new(Han::new(1), 30, true, WinMethod::Ron, 0, 0, &rules);
+        assert_eq!(points.payment, Payment::Ron(1500));
+        let points = Points::new(Han::new(1), 30, false, WinMethod::Tsumo, 0, 0, &rules);
+        assert_eq!(
+            points.payment,
+            Payment::Tsumo {
+                dealer: 500,
+                non_dealer: 300
+            }
+        );
+        assert_eq!(points.total(), 1100);
+        let points = Points::new(Han::new(2), 40, true, WinMethod::Tsumo, 0, 0, &rules);
+        assert_eq!(points.payment, Payment::DealerTsumo(1300));
+        assert_eq!(points.total(), 3900);
+
+        let points = Points::new(Han::new(4), 30, false, WinMethod::Ron, 2, 1, &rules);
+        assert_eq!(points.payment, Payment::Ron(7700 + 600));
+        assert_eq!(points.total(), 8300 + 1000);
+        let points = Points::new(Han::new(5), 30, false, WinMethod::Tsumo, 1, 0, &rules);
+        assert_eq!(
+            points.payment,
+            Payment::Tsumo {
+                dealer: 4100,
+                non_dealer: 2100
+            }
+        );
+        let points = Points::new(Han::yakuman(), 30, true, WinMethod::Tsumo, 0, 2, &rules);
+        assert_eq!(points.payment, Payment::DealerTsumo(16000));
+        assert_eq!(points.total(), 50000);
+
+        let points = Points::limited(Limit::Mangan, false, WinMethod::Ron, 1, 0);
+        assert_eq!(points.payment, Payment::Ron(8300));
+        assert_eq!(points.limit, Some(Limit::Mangan));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{ready_set::ReadyTileSet, shanten::tile_counts, tile::Tile, yaku::Yaku};
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::Instant,
+};
+
+/// how much work `ReadyTileSet::search` may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    /// hands expanded, a hand reached twice is expanded once
+    Nodes(usize),
+    Deadline(Instant),
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::Nodes(10_000)
+    }
+}
+
+/// effective first draws and the wins they lead to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Search {
+    pub shanten: i8,
+    /// every draw that lowers the shanten, complete even when the budget runs out
+    pub draws: Vec<Tile>,
+    /// first draw and the yaku of a win reached from it, sorted and deduplicated
+    pub wins: Vec<(Tile, Vec<Yaku>)>,
+    /// some hands were not expanded, so `wins` may miss entries
+    pub exhausted: bool,
+}
+
+/// depth first through draws and discards that lower the shanten,
+/// the wins below each hand are kept by its tile counts
+pub(crate) struct Searcher {
+    budget: Budget,
+    nodes: usize,
+    exhausted: bool,
+    visited: HashMap<[u8; 34], BTreeSet<Vec<Yaku>>>,
+}
+
+impl Searcher {
+    pub(crate) fn new(budget: Budget) -> Self {
+        Searcher {
+            budget,
+            nodes: 0,
+            exhausted: false,
+            visited: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// yaku of every win reachable after drawing `draw`
+    pub(crate) fn after_draw(
+        &mut self,
+        ready_set: &ReadyTileSet,
+        shanten: i8,
+        draw: Tile,
+    ) -> BTreeSet<Vec<Yaku>> {
+        let full_set = ready_set.draw(draw);
+        if let Some(yakus) = full_set.yakus() {
+            return BTreeSet::from([yakus]);
+        }
+        let mut wins = BTreeSet::new();
+        if shanten == 0 {
+            return wins;
+        }
+
+        let mut discards = full_set.concealed().to_vec();
+        discards.dedup();
+        for discard in discards {
+            let next = full_set.discard(discard).unwrap();
+            let next_shanten = next.shanten();
+            if next_shanten < shanten {
+                wins.extend(self.expand(&next, next_shanten));
+            }
+        }
+        wins
+    }
+
+    fn expand(&mut self, ready_set: &ReadyTileSet, shanten: i8) -> BTreeSet<Vec<Yaku>> {
+        let key = tile_counts(ready_set.concealed());
+        if let Some(wins) = self.visited.get(&key) {
+            return wins.clone();
+        }
+        if self.out_of_budget() {
+            return BTreeSet::new();
+        }
+        self.nodes += 1;
+
+        let mut wins = BTreeSet::new();
+        for draw in ready_set.effective_draws() {
+            wins.extend(self.after_draw(ready_set, shanten, draw));
+        }
+        self.visited.insert(key, wins.clone());
+        wins
+    }
+
+    fn out_of_budget(&mut self) -> bool {
+        let out = match self.budget {
+            Budget::Nodes(max) => self.nodes >= max,
+            Budget::Deadline(deadline) => Instant::now() >= deadline,
+        };
+        self.exhausted |= out;
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn budget() {
+        let tileset = ReadyTileSet::from_str("147p 258m 369s ton nan shaa pei").unwrap();
+        let search = tileset.search(Budget::Nodes(50));
+        assert!(search.shanten > 2);
+        assert!(search.exhausted);
+        assert_eq!(search.draws, tileset.effective_draws());
+        assert!(!search.draws.is_empty());
+
+        let search = ReadyTileSet::from_str("23p 456m 345s 678s 5p chun")
+            .unwrap()
+            .search(Budget::default());
+        assert_eq!(search.shanten, 1);
+        assert!(!search.exhausted);
+        assert_eq!(search.draws, vec![T_1P, T_2P, T_4P, T_5P, T_CHUN]);
+        let has_win = |draw: Tile, yaku: Yaku| {
+            search
+                .wins
+                .iter()
+                .any(|(tile, yakus)| *tile == draw && yakus.contains(&yaku))
+        };
+        assert!(has_win(T_5P, Yaku::Pinfu));
+        assert!(has_win(T_4P, Yaku::Tanyao));
+        assert!(!has_win(T_CHUN, Yaku::Pinfu));
+    }
+
+    #[test]
+    fn deadline() {
+        let tenpai = ReadyTileSet::from_str("123p 456m 789s 1s2 45p").unwrap();
+        let search = tenpai.search(Budget::Deadline(Instant::now()));
+        assert!(!search.exhausted);
+        assert_eq!(search.wins.len(), 2);
+
+        let search = ReadyTileSet::from_str("124578p 124578m 1s")
+            .unwrap()
+            .search(Budget::Deadline(Instant::now()));
+        assert!(search.exhausted);
+        assert!(search.wins.is_empty());
+    }
+}
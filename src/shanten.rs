@@ -0,0 +1,239 @@
+//! shanten over tile counts, each suit is looked up by its counts
+
+use crate::{tile::Tile, T_INVALID};
+use std::{cell::RefCell, collections::HashMap};
+
+/// best taatsu number of a suit, indexed by `[has_pair][mentsu]`, -1 if impossible
+type Entry = [[i8; 5]; 2];
+
+const IMPOSSIBLE: Entry = [[-1; 5]; 2];
+
+thread_local! {
+    /// entries of the numbered suits seen so far, keyed by base 5 digits
+    static SUIT_ENTRIES: RefCell<HashMap<usize, Entry>> = RefCell::new(HashMap::new());
+    /// entries of the honors seen so far, which never form sequences
+    static HONOR_ENTRIES: RefCell<HashMap<usize, Entry>> = RefCell::new(HashMap::new());
+}
+
+/// count of each tile, indexed as `ALL_TILES`, `T_INVALID` padding is skipped
+pub fn tile_counts(tiles: &[Tile]) -> [u8; 34] {
+    let mut counts = [0; 34];
+    for tile in tiles.iter().filter(|&&tile| tile != T_INVALID) {
+        counts[tile.index()] += 1;
+    }
+    counts
+}
+
+/// the least shanten of all three forms, -1 for a complete hand
+pub fn shanten(counts: &[u8; 34], meld_num: u8) -> i8 {
+    let regular = regular_shanten(counts, meld_num);
+    if meld_num == 0 {
+        regular
+            .min(chiitoitsu_shanten(counts))
+            .min(kokushi_shanten(counts))
+    } else {
+        regular
+    }
+}
+
+/// four mentsu and a pair, `meld_num` of the mentsu are called
+pub fn regular_shanten(counts: &[u8; 34], meld_num: u8) -> i8 {
+    let block_limit = 4 - meld_num as usize;
+    let suit = |counts: &[u8]| {
+        SUIT_ENTRIES.with(|cache| entry(&mut cache.borrow_mut(), suit_key(counts), true))
+    };
+    let entries = [
+        suit(&counts[0..9]),
+        suit(&counts[9..18]),
+        suit(&counts[18..27]),
+        HONOR_ENTRIES
+            .with(|cache| entry(&mut cache.borrow_mut(), suit_key(&counts[27..34]), false)),
+    ];
+
+    // most taatsu with `[has_pair][mentsu]` over the suits so far
+    let mut best = IMPOSSIBLE;
+    best[0][0] = 0;
+    for entry in entries {
+        let mut next = IMPOSSIBLE;
+        for (pair, mentsu) in (0..2).flat_map(|pair| (0..=block_limit).map(move |m| (pair, m))) {
+            if best[pair][mentsu] < 0 {
+                continue;
+            }
+            for (suit_pair, suit_mentsu) in
+                (0..2 - pair).flat_map(|p| (0..=block_limit - mentsu).map(move |m| (p, m)))
+            {
+                let taatsu = entry[suit_pair][suit_mentsu];
+                if taatsu >= 0 {
+                    let slot = &mut next[pair + suit_pair][mentsu + suit_mentsu];
+                    *slot = (*slot).max(best[pair][mentsu] + taatsu);
+                }
+            }
+        }
+        best = next;
+    }
+
+    let mut max_value = 0;
+    for (pair, row) in best.iter().enumerate() {
+        for (mentsu, &taatsu) in row.iter().enumerate().take(block_limit + 1) {
+            if taatsu >= 0 {
+                let taatsu = (taatsu as usize).min(block_limit - mentsu);
+                max_value = max_value.max(2 * mentsu + taatsu + pair);
+            }
+        }
+    }
+    8 - 2 * meld_num as i8 - max_value as i8
+}
+
+/// seven different pairs
+pub fn chiitoitsu_shanten(counts: &[u8; 34]) -> i8 {
+    let pairs = counts.iter().filter(|&&count| count >= 2).count() as i8;
+    let kinds = counts.iter().filter(|&&count| count >= 1).count() as i8;
+    6 - pairs + (7 - kinds).max(0)
+}
+
+/// thirteen orphans
+pub fn kokushi_shanten(counts: &[u8; 34]) -> i8 {
+    let yaochuus = counts
+        .iter()
+        .enumerate()
+        .filter(|&(index, _)| index >= 27 || index % 9 == 0 || index % 9 == 8)
+        .map(|(_, &count)| count);
+    let kinds = yaochuus.clone().filter(|&count| count >= 1).count() as i8;
+    let has_pair = yaochuus.clone().any(|count| count >= 2);
+    13 - kinds - has_pair as i8
+}
+
+fn suit_key(counts: &[u8]) -> usize {
+    counts
+        .iter()
+        .rev()
+        .fold(0, |key, &count| key * 5 + count as usize)
+}
+
+/// built from the entries left after taking a block from the lowest rank,
+/// every entry computed on the way is kept in `cache`
+fn entry(cache: &mut HashMap<usize, Entry>, key: usize, sequence: bool) -> Entry {
+    if key == 0 {
+        let mut entry = IMPOSSIBLE;
+        entry[0][0] = 0;
+        return entry;
+    }
+    if let Some(&entry) = cache.get(&key) {
+        return entry;
+    }
+    let count = |rank: u32| key / 5usize.pow(rank) % 5;
+    let low = (0..).find(|&rank| count(rank) > 0).unwrap();
+    let unit = 5usize.pow(low);
+    // ranks past the last one count 0
+    let has = |offset: u32| count(low + offset) > 0;
+
+    let mut result = IMPOSSIBLE;
+    let mut update = |from: Entry, mentsu: usize, pair: usize, taatsu: i8| {
+        for (from_pair, row) in from.iter().enumerate() {
+            for (from_mentsu, &from_taatsu) in row.iter().enumerate() {
+                let (to_pair, to_mentsu) = (from_pair + pair, from_mentsu + mentsu);
+                if from_taatsu >= 0 && to_pair < 2 && to_mentsu < 5 {
+                    let slot = &mut result[to_pair][to_mentsu];
+                    *slot = (*slot).max(from_taatsu + taatsu);
+                }
+            }
+        }
+    };
+
+    // the lowest tile left alone
+    update(entry(cache, key - unit, sequence), 0, 0, 0);
+    if count(low) >= 2 {
+        let from = entry(cache, key - 2 * unit, sequence);
+        update(from, 0, 1, 0);
+        update(from, 0, 0, 1);
+    }
+    if count(low) >= 3 {
+        update(entry(cache, key - 3 * unit, sequence), 1, 0, 0);
+    }
+    if sequence && has(1) {
+        update(entry(cache, key - unit - unit * 5, sequence), 0, 0, 1);
+    }
+    if sequence && has(2) {
+        update(entry(cache, key - unit - unit * 25, sequence), 0, 0, 1);
+    }
+    if sequence && has(1) && has(2) {
+        update(
+            entry(cache, key - unit - unit * 5 - unit * 25, sequence),
+            1,
+            0,
+            0,
+        );
+    }
+    cache.insert(key, result);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ReadyTileSet, T_4M, T_4P, T_5P, T_6P, T_7P, T_8P, T_9P, T_CHUN, T_HAKU, T_INVALID,
+    };
+    use std::str::FromStr;
+
+    fn counts(s: &str) -> [u8; 34] {
+        let tileset = ReadyTileSet::from_str(s).unwrap();
+        tile_counts(tileset.concealed())
+    }
+
+    #[test]
+    fn invalid_padding() {
+        let tileset = ReadyTileSet::from_str("123p 456m 789s haku [ton4]").unwrap();
+        assert_eq!(
+            tile_counts(&tileset.tiles),
+            counts("123p 456m 789s haku [ton4]")
+        );
+        assert_eq!(tile_counts(&[T_INVALID; 14]), [0; 34]);
+    }
+
+    #[test]
+    fn regular() {
+        assert_eq!(regular_shanten(&counts("123456789p 1234m"), 0), 0);
+        assert_eq!(regular_shanten(&counts("1p3 2p3 3p 4p3 1m3"), 0), 0);
+        assert_eq!(regular_shanten(&counts("124578p 124578m 1s"), 0), 4);
+        assert_eq!(
+            regular_shanten(&counts("147p 258m 369s ton nan shaa pei"), 0),
+            8
+        );
+        assert_eq!(regular_shanten(&counts("1111222233334p"), 0), 0);
+
+        let mut complete = counts("123456789p 1234m");
+        complete[T_4M.index()] += 1;
+        assert_eq!(regular_shanten(&complete, 0), -1);
+
+        let open = tile_counts(&[T_4P, T_5P, T_6P, T_7P, T_8P, T_9P, T_9P]);
+        assert_eq!(regular_shanten(&open, 2), 0);
+        assert_eq!(regular_shanten(&tile_counts(&[T_CHUN]), 4), 0);
+        assert_eq!(
+            regular_shanten(&tile_counts(&[T_CHUN, T_HAKU, T_HAKU, T_HAKU]), 3),
+            0
+        );
+    }
+
+    #[test]
+    fn chiitoitsu() {
+        assert_eq!(chiitoitsu_shanten(&counts("1p2 2s2 3m2 4p2 5s2 6m2 7p")), 0);
+        assert_eq!(chiitoitsu_shanten(&counts("1p3 1p 2s2 3m2 4p2 5s2 6m")), 2);
+        assert_eq!(shanten(&counts("1p2 2s2 3m2 4p2 5s2 6m2 7p"), 0), 0);
+    }
+
+    #[test]
+    fn kokushi() {
+        let tiles = counts("19p 19s 19m ton nan shaa pei haku hatsu chun");
+        assert_eq!(kokushi_shanten(&tiles), 0);
+        assert_eq!(shanten(&tiles, 0), 0);
+        assert_eq!(
+            kokushi_shanten(&counts("19p 19s 19m ton2 shaa pei haku hatsu chun")),
+            0
+        );
+        assert_eq!(
+            kokushi_shanten(&counts("19p 19s 19m ton3 pei haku hatsu chun")),
+            1
+        );
+    }
+}
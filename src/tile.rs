@@ -1,4 +1,4 @@
-use anyhow::{anyhow, Error, Result};
+use crate::error::{Error, ParseError};
 use std::{
     fmt::{Debug, Display},
     str::FromStr,
@@ -77,26 +77,151 @@ pub const T_HATSU: Tile = ALL_TILES[32];
 pub const T_CHUN: Tile = ALL_TILES[33];
 pub const T_INVALID: Tile = Tile(0xff);
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// glyphs of ton, nan, shaa, pei, haku, hatsu and chun in the Mahjong Tiles block
+const UNICODE_HONORS: [char; 7] = ['🀀', '🀁', '🀂', '🀃', '🀆', '🀅', '🀄'];
+/// glyphs of 1m, 1p and 1s, the other numbers follow
+const UNICODE_ONES: [char; 3] = ['🀇', '🀙', '🀐'];
+const KANJI_NUMBERS: [char; 9] = ['一', '二', '三', '四', '五', '六', '七', '八', '九'];
+const CIRCLED_NUMBERS: [char; 9] = ['①', '②', '③', '④', '⑤', '⑥', '⑦', '⑧', '⑨'];
+const KANJI_HONORS: [char; 7] = ['東', '南', '西', '北', '白', '發', '中'];
+
+/// red fives, equal to normal fives but still dora
+pub const T_0M: Tile = Tile(0x00);
+pub const T_0P: Tile = Tile(0x10);
+pub const T_0S: Tile = Tile(0x20);
+
+/// 花色, all honors share one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Suit {
+    /// 万子
+    Man,
+    /// 筒子
+    Pin,
+    /// 索子
+    Sou,
+    /// 字牌
+    Honor,
+}
+
+/// 风牌, in the order of seats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Wind {
+    /// 东
+    Ton,
+    /// 南
+    Nan,
+    /// 西
+    Shaa,
+    /// 北
+    Pei,
+}
+
+/// 三元牌
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dragon {
+    /// 白
+    Haku,
+    /// 发
+    Hatsu,
+    /// 中
+    Chun,
+}
+
+impl Wind {
+    pub const ALL: [Wind; 4] = [Wind::Ton, Wind::Nan, Wind::Shaa, Wind::Pei];
+
+    /// the seat to the right, 北 goes back to 东
+    pub fn next(&self) -> Wind {
+        Wind::ALL[(*self as usize + 1) % 4]
+    }
+}
+
+impl Dragon {
+    pub const ALL: [Dragon; 3] = [Dragon::Haku, Dragon::Hatsu, Dragon::Chun];
+
+    /// 中 goes back to 白, as dora indicators do
+    pub fn next(&self) -> Dragon {
+        Dragon::ALL[(*self as usize + 1) % 3]
+    }
+}
+
+impl From<Wind> for Tile {
+    fn from(wind: Wind) -> Self {
+        ALL_TILES[27 + wind as usize]
+    }
+}
+
+impl From<Dragon> for Tile {
+    fn from(dragon: Dragon) -> Self {
+        ALL_TILES[31 + dragon as usize]
+    }
+}
+
+impl TryFrom<Tile> for Wind {
+    type Error = Error;
+
+    fn try_from(tile: Tile) -> Result<Self, Error> {
+        tile.wind().ok_or(Error::NotAWind(tile))
+    }
+}
+
+impl TryFrom<Tile> for Dragon {
+    type Error = Error;
+
+    fn try_from(tile: Tile) -> Result<Self, Error> {
+        tile.dragon().ok_or(Error::NotADragon(tile))
+    }
+}
+
+/// high 4 bits for type, low 4 bits for number, red fives are numbered 0
+#[derive(Clone, Copy)]
 pub struct Tile(u8);
 
+impl PartialEq for Tile {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for Tile {}
+
+impl Ord for Tile {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.normalized().cmp(&other.normalized())
+    }
+}
+
+impl PartialOrd for Tile {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl FromStr for Tile {
-    type Err = Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
+    /// also a glyph such as `🀅` or kanji such as `⑨筒`
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        if !s.is_ascii() {
+            let mut chars = s.chars();
+            return match (chars.next().and_then(Tile::from_unicode), chars.next()) {
+                (Some(tile), None) => Ok(tile),
+                _ => Tile::from_kanji(s),
+            };
+        }
         if s.len() < 2 {
-            return Err(anyhow!("\"{}\" is too short to be a tile", s));
+            return Err(ParseError::unknown_tile(s));
         }
 
         let bytes = s.as_bytes();
-        let res = if bytes[0] >= b'1' && bytes[0] <= b'9' {
+        let res = if bytes[0] >= b'0' && bytes[0] <= b'9' {
             (match bytes[1] {
                 b'm' => 0,
                 b'p' => 1,
                 b's' => 2,
-                _ => return Err(anyhow!("\"{}\" is numbered but not m/s/p", s)),
+                _ => return Err(ParseError::unknown_tile(s)),
             } << 4)
-                + bytes[0] as u8
+                + bytes[0]
                 - b'0'
         } else {
             (match s {
@@ -107,7 +232,7 @@ impl FromStr for Tile {
                 "haku" => 7,
                 "hatsu" => 8,
                 "chun" => 9,
-                _ => return Err(anyhow!("\"{}\" is not a tile", s)),
+                _ => return Err(ParseError::unknown_tile(s)),
             }) << 4
         };
         Ok(Tile(res))
@@ -122,7 +247,7 @@ impl Display for Tile {
 
         if *self == T_INVALID {
             write!(f, "INVALID")
-        } else if self.0 & 0xf == 0 {
+        } else if self.is_honor() {
             write!(f, "{}", HIGH_BITS[(self.0 >> 4) as usize])
         } else {
             write!(f, "{}{}", self.0 & 0xf, HIGH_BITS[(self.0 >> 4) as usize])
@@ -137,8 +262,126 @@ impl Debug for Tile {
 }
 
 impl Tile {
+    fn normalized(&self) -> u8 {
+        if self.is_red() {
+            self.0 | 5
+        } else {
+            self.0
+        }
+    }
+
+    /// `1m` to `9m`, `0m` for a red five, `1z` to `7z` for east, south, west,
+    /// north, haku, hatsu and chun
+    pub fn from_mpsz(s: &str) -> Result<Self, ParseError> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 2 || !bytes[0].is_ascii_digit() {
+            return Err(ParseError::unknown_tile(s));
+        }
+        let number = bytes[0] - b'0';
+        match bytes[1] {
+            b'm' => Ok(Tile(number)),
+            b'p' => Ok(Tile(0x10 | number)),
+            b's' => Ok(Tile(0x20 | number)),
+            b'z' if (1..=7).contains(&number) => Ok(Tile((number + 2) << 4)),
+            _ => Err(ParseError::unknown_tile(s)),
+        }
+    }
+
+    /// reverse of `from_mpsz`
+    pub fn to_mpsz(&self) -> String {
+        if self.is_honor() {
+            format!("{}z", self.tile_type() - 2)
+        } else {
+            let suit = ["m", "p", "s"][self.tile_type() as usize];
+            format!("{}{}", self.0 & 0xf, suit)
+        }
+    }
+
+    /// glyph in the Mahjong Tiles block, a red five has none of its own
+    pub fn to_unicode(&self) -> char {
+        let code = if self.is_honor() {
+            UNICODE_HONORS[self.index() - 27] as u32
+        } else {
+            UNICODE_ONES[self.tile_type() as usize] as u32 + self.number() as u32 - 1
+        };
+        char::from_u32(code).unwrap()
+    }
+
+    pub fn from_unicode(ch: char) -> Option<Self> {
+        if let Some(index) = UNICODE_HONORS.iter().position(|&honor| honor == ch) {
+            return Some(ALL_TILES[27 + index]);
+        }
+        UNICODE_ONES.iter().enumerate().find_map(|(ty, &one)| {
+            let offset = (ch as u32).checked_sub(one as u32)?;
+            (offset < 9).then(|| ALL_TILES[ty * 9 + offset as usize])
+        })
+    }
+
+    /// `一萬`, `⑨筒`, `五索` or `發`, a red five starts with `赤`
+    pub fn to_kanji(&self) -> String {
+        if self.is_honor() {
+            return KANJI_HONORS[self.index() - 27].to_string();
+        }
+        let index = self.number() as usize - 1;
+        let text = match self.tile_type() {
+            0 => format!("{}萬", KANJI_NUMBERS[index]),
+            1 => format!("{}筒", CIRCLED_NUMBERS[index]),
+            _ => format!("{}索", KANJI_NUMBERS[index]),
+        };
+        if self.is_red() {
+            format!("赤{text}")
+        } else {
+            text
+        }
+    }
+
+    /// reverse of `to_kanji`, also accepts `万` and `発`
+    pub fn from_kanji(s: &str) -> Result<Self, ParseError> {
+        let (red, rest) = match s.strip_prefix('赤') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let chars = rest.chars().collect::<Vec<_>>();
+        let tile = match chars[..] {
+            ['発'] if !red => T_HATSU,
+            [honor] if !red => KANJI_HONORS
+                .iter()
+                .position(|&kanji| kanji == honor)
+                .map(|index| ALL_TILES[27 + index])
+                .ok_or_else(|| ParseError::unknown_tile(s))?,
+            [number, suit] => {
+                let (ty, numbers) = match suit {
+                    '萬' | '万' => (0, &KANJI_NUMBERS),
+                    '筒' => (1, &CIRCLED_NUMBERS),
+                    '索' => (2, &KANJI_NUMBERS),
+                    _ => return Err(ParseError::unknown_tile(s)),
+                };
+                let index = numbers
+                    .iter()
+                    .position(|&ch| ch == number)
+                    .ok_or_else(|| ParseError::unknown_tile(s))?;
+                ALL_TILES[ty * 9 + index]
+            }
+            _ => return Err(ParseError::unknown_tile(s)),
+        };
+        match (red, tile.number()) {
+            (false, _) => Ok(tile),
+            (true, 5) => Ok(Tile(tile.0 & 0xf0)),
+            (true, _) => Err(ParseError::unknown_tile(s)),
+        }
+    }
+
+    pub fn is_red(&self) -> bool {
+        self.0 & 0xf == 0 && self.0 >> 4 < 3
+    }
+
+    /// a red five becomes a normal five
+    pub fn to_normal(&self) -> Tile {
+        Tile(self.normalized())
+    }
+
     pub fn is_honor(&self) -> bool {
-        self.0 & 0xf == 0
+        self.0 & 0xf == 0 && !self.is_red()
     }
 
     pub fn is_terminal(&self) -> bool {
@@ -150,11 +393,34 @@ impl Tile {
     }
 
     pub fn is_dragon(&self) -> bool {
-        *self == T_HAKU || *self == T_HATSU || *self == T_CHUN
+        self.dragon().is_some()
     }
 
     pub fn is_wind(&self) -> bool {
-        self.is_honor() && !self.is_dragon()
+        self.wind().is_some()
+    }
+
+    pub fn suit(&self) -> Suit {
+        match self.tile_type() {
+            0 => Suit::Man,
+            1 => Suit::Pin,
+            2 => Suit::Sou,
+            _ => Suit::Honor,
+        }
+    }
+
+    pub fn wind(&self) -> Option<Wind> {
+        match self.index() {
+            index @ 27..=30 => Some(Wind::ALL[index - 27]),
+            _ => None,
+        }
+    }
+
+    pub fn dragon(&self) -> Option<Dragon> {
+        match self.index() {
+            index @ 31..=33 => Some(Dragon::ALL[index - 31]),
+            _ => None,
+        }
     }
 
     pub fn is_related(&self, other: Tile) -> bool {
@@ -162,25 +428,46 @@ impl Tile {
             && (self.number() as i8 - other.number() as i8).abs() <= 2
     }
 
+    /// position in `ALL_TILES`, a red five shares it with the normal five
+    pub fn index(&self) -> usize {
+        match self.tile_type() {
+            ty @ 0..=2 => ty as usize * 9 + self.number() as usize - 1,
+            ty => 27 + ty as usize - 3,
+        }
+    }
+
     pub fn tile_type(&self) -> u8 {
         self.0 >> 4
     }
 
     pub fn number(&self) -> u8 {
-        self.0 & 0xf
+        self.normalized() & 0xf
     }
 
     pub fn next(&self) -> Option<Tile> {
         if self.is_numbered() && self.number() < 9 {
-            Some(Tile(self.0 + 1))
+            Some(Tile(self.normalized() + 1))
         } else {
             None
         }
     }
 
+    /// the dora indicated by this tile
+    pub fn indicated_dora(&self) -> Tile {
+        if self.is_numbered() {
+            self.next().unwrap_or(Tile(self.0 & 0xf0 | 1))
+        } else if let Some(wind) = self.wind() {
+            wind.next().into()
+        } else if let Some(dragon) = self.dragon() {
+            dragon.next().into()
+        } else {
+            T_INVALID
+        }
+    }
+
     pub fn prev(&self) -> Option<Tile> {
         if self.is_numbered() && self.number() > 1 {
-            Some(Tile(self.0 - 1))
+            Some(Tile(self.normalized() - 1))
         } else {
             None
         }
@@ -198,18 +485,126 @@ mod tests {
     ];
 
     #[test]
-    fn from_str() -> Result<()> {
+    fn from_str() -> Result<(), ParseError> {
         for tile_str in ALL_TILE_STRS {
             let tile = Tile::from_str(tile_str)?;
             assert_eq!(tile.to_string(), tile_str);
         }
-        let not_tiles = ["foo", "bar", "0s", "5q", "10"];
+        let not_tiles = ["foo", "bar", "0z", "5q", "10"];
         for not_tile_str in not_tiles {
             assert!(Tile::from_str(not_tile_str).is_err());
         }
         Ok(())
     }
 
+    #[test]
+    fn mpsz() {
+        for tile in ALL_TILES {
+            assert_eq!(Tile::from_mpsz(&tile.to_mpsz()).unwrap(), tile);
+        }
+        assert_eq!(T_9P.to_mpsz(), "9p");
+        assert_eq!(T_TON.to_mpsz(), "1z");
+        assert_eq!(T_CHUN.to_mpsz(), "7z");
+        assert_eq!(T_0S.to_mpsz(), "0s");
+        assert!(Tile::from_mpsz("0m").unwrap().is_red());
+        for not_tile in ["8z", "0z", "1", "11m", "m1", "ton"] {
+            assert!(Tile::from_mpsz(not_tile).is_err());
+        }
+    }
+
+    #[test]
+    fn unicode_and_kanji() -> Result<(), ParseError> {
+        for tile in ALL_TILES {
+            assert_eq!(Tile::from_unicode(tile.to_unicode()), Some(tile));
+            assert_eq!(Tile::from_kanji(&tile.to_kanji())?, tile);
+            assert_eq!(Tile::from_str(&tile.to_unicode().to_string())?, tile);
+            assert_eq!(Tile::from_str(&tile.to_kanji())?, tile);
+        }
+        assert_eq!(T_1M.to_unicode(), '🀇');
+        assert_eq!(T_9P.to_unicode(), '🀡');
+        assert_eq!(T_9S.to_unicode(), '🀘');
+        assert_eq!(T_CHUN.to_unicode(), '🀄');
+        assert_eq!(T_0P.to_unicode(), T_5P.to_unicode());
+        assert_eq!(T_1M.to_kanji(), "一萬");
+        assert_eq!(T_9P.to_kanji(), "⑨筒");
+        assert_eq!(T_HATSU.to_kanji(), "發");
+        assert_eq!(T_0S.to_kanji(), "赤五索");
+        assert!(Tile::from_kanji("赤五萬")?.is_red());
+        assert_eq!(Tile::from_str("発")?, T_HATSU);
+        assert_eq!(Tile::from_str("三万")?, T_3M);
+        for not_tile in ["赤一萬", "赤中", "⑩筒", "一", "🀪", "東東"] {
+            assert!(Tile::from_str(not_tile).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn red_five() -> Result<(), ParseError> {
+        for (tile_str, red, normal) in [("0m", T_0M, T_5M), ("0p", T_0P, T_5P), ("0s", T_0S, T_5S)]
+        {
+            let tile = Tile::from_str(tile_str)?;
+            assert_eq!(tile.to_string(), tile_str);
+            assert!(tile.is_red());
+            assert!(tile.is_numbered());
+            assert_eq!(tile, red);
+            assert_eq!(tile, normal);
+            assert_eq!(tile.to_normal().to_string(), normal.to_string());
+            assert_eq!(tile.number(), 5);
+            assert!(tile.is_related(normal.next().unwrap()));
+            assert_eq!(tile.next(), normal.next());
+            assert_eq!(tile.prev(), normal.prev());
+            assert_eq!(tile.indicated_dora(), normal.next().unwrap());
+            assert!(!normal.is_red());
+        }
+        assert!(T_0M < T_6M && T_0M > T_4M);
+        Ok(())
+    }
+
+    #[test]
+    fn indicated_dora() {
+        assert_eq!(T_1M.indicated_dora(), T_2M);
+        assert_eq!(T_9M.indicated_dora(), T_1M);
+        assert_eq!(T_9P.indicated_dora(), T_1P);
+        assert_eq!(T_9S.indicated_dora(), T_1S);
+        assert_eq!(T_TON.indicated_dora(), T_NAN);
+        assert_eq!(T_PEI.indicated_dora(), T_TON);
+        assert_eq!(T_HAKU.indicated_dora(), T_HATSU);
+        assert_eq!(T_CHUN.indicated_dora(), T_HAKU);
+    }
+
+    #[test]
+    fn suit_wind_dragon() {
+        assert_eq!(T_0M.suit(), Suit::Man);
+        assert_eq!(T_9P.suit(), Suit::Pin);
+        assert_eq!(T_1S.suit(), Suit::Sou);
+        assert_eq!(T_HAKU.suit(), Suit::Honor);
+        for (wind, tile) in Wind::ALL.into_iter().zip([T_TON, T_NAN, T_SHAA, T_PEI]) {
+            assert_eq!(Tile::from(wind), tile);
+            assert_eq!(tile.wind(), Some(wind));
+            assert_eq!(Wind::try_from(tile), Ok(wind));
+            assert_eq!(tile.dragon(), None);
+        }
+        for (dragon, tile) in Dragon::ALL.into_iter().zip([T_HAKU, T_HATSU, T_CHUN]) {
+            assert_eq!(Tile::from(dragon), tile);
+            assert_eq!(tile.dragon(), Some(dragon));
+            assert_eq!(Dragon::try_from(tile), Ok(dragon));
+            assert_eq!(tile.wind(), None);
+        }
+        assert_eq!(Wind::Pei.next(), Wind::Ton);
+        assert_eq!(Dragon::Chun.next(), Dragon::Haku);
+        assert_eq!(T_5M.wind(), None);
+        assert_eq!(Wind::try_from(T_HAKU), Err(Error::NotAWind(T_HAKU)));
+        assert_eq!(Dragon::try_from(T_1P), Err(Error::NotADragon(T_1P)));
+    }
+
+    #[test]
+    fn index() {
+        for (index, tile) in ALL_TILES.iter().enumerate() {
+            assert_eq!(tile.index(), index);
+        }
+        assert_eq!(T_0S.index(), T_5S.index());
+    }
+
     #[test]
     fn tile_ord() {
         let mut v = ALL_TILE_STRS
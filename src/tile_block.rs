@@ -1,89 +1,161 @@
-use crate::{Tile, T_INVALID};
-use anyhow::{anyhow, Result};
+use crate::{
+    error::{Error, Result},
+    Tile, T_INVALID,
+};
+use std::fmt::Display;
 
+/// a meld, a pair, or a single tile of kokushi
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TileBlock {
-    tiles: [Tile; 3],
+    tiles: [Tile; 4],
     block_type: BlockType,
+    is_open: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum BlockType {
+    Quad,
     Triplet,
     Sequence,
     Pair,
-    Incompleted,
     Orphan,
 }
 
+/// kind of a block in a decomposition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BlockKind {
+    /// 杠子
+    Quad,
+    /// 刻子
+    Triplet,
+    /// 顺子
+    Sequence,
+    /// 对子
+    Pair,
+    /// 国士无双的单张
+    Single,
+}
+
 impl TileBlock {
-    pub fn new_triplet(tiles: [Tile; 3]) -> Result<Self> {
-        if tiles[0] == tiles[1] && tiles[0] == tiles[2] {
+    pub(crate) fn new_quad(tiles: [Tile; 4]) -> Result<Self> {
+        if tiles.iter().all(|&tile| tile == tiles[0]) {
             Ok(TileBlock {
                 tiles,
+                block_type: BlockType::Quad,
+                is_open: false,
+            })
+        } else {
+            Err(Error::InvalidBlock(tiles.to_vec()))
+        }
+    }
+
+    pub(crate) fn new_triplet(tiles: [Tile; 3]) -> Result<Self> {
+        if tiles[0] == tiles[1] && tiles[0] == tiles[2] {
+            Ok(TileBlock {
+                tiles: [tiles[0], tiles[1], tiles[2], T_INVALID],
                 block_type: BlockType::Triplet,
+                is_open: false,
             })
         } else {
-            Err(anyhow!("{:?} is not a triplet", tiles))
+            Err(Error::InvalidBlock(tiles.to_vec()))
         }
     }
 
-    pub fn new_sequence(tiles: [Tile; 3]) -> Result<Self> {
+    pub(crate) fn new_sequence(tiles: [Tile; 3]) -> Result<Self> {
         if tiles[0].tile_type() == tiles[1].tile_type()
             && tiles[0].tile_type() == tiles[2].tile_type()
             && tiles[0].number() + 1 == tiles[1].number()
             && tiles[0].number() + 2 == tiles[2].number()
         {
             Ok(TileBlock {
-                tiles,
+                tiles: [tiles[0], tiles[1], tiles[2], T_INVALID],
                 block_type: BlockType::Sequence,
+                is_open: false,
             })
         } else {
-            Err(anyhow!("{:?} is not a sequence", tiles))
+            Err(Error::InvalidBlock(tiles.to_vec()))
         }
     }
 
-    pub fn new_pair(tiles: [Tile; 2]) -> Result<Self> {
+    pub(crate) fn new_pair(tiles: [Tile; 2]) -> Result<Self> {
         if tiles[0] == tiles[1] {
             Ok(TileBlock {
-                tiles: [tiles[0], tiles[1], T_INVALID],
+                tiles: [tiles[0], tiles[1], T_INVALID, T_INVALID],
                 block_type: BlockType::Pair,
+                is_open: false,
             })
         } else {
-            Err(anyhow!("{:?} is not a pair", tiles))
-        }
-    }
-
-    pub fn new_incompleted(tiles: [Tile; 2]) -> Result<Self> {
-        if tiles[0].is_related(tiles[1]) {
-            Ok(TileBlock {
-                tiles: [tiles[0], tiles[1], T_INVALID],
-                block_type: BlockType::Incompleted,
-            })
-        } else {
-            Err(anyhow!("{:?} is not a incompleted", tiles))
+            Err(Error::InvalidBlock(tiles.to_vec()))
         }
     }
 
-    pub fn new_orphan(tile: Tile) -> Result<Self> {
+    pub(crate) fn new_orphan(tile: Tile) -> Result<Self> {
         Ok(TileBlock {
-            tiles: [tile, T_INVALID, T_INVALID],
+            tiles: [tile, T_INVALID, T_INVALID, T_INVALID],
             block_type: BlockType::Orphan,
+            is_open: false,
         })
     }
 
-    pub fn new_unknown(tiles: &[Tile]) -> Result<Self> {
+    pub(crate) fn new_unknown(tiles: &[Tile]) -> Result<Self> {
         match tiles.len() {
             1 => TileBlock::new_orphan(tiles[0]),
-            2 => TileBlock::new_pair(tiles.try_into()?),
-            3 => TileBlock::new_triplet(tiles.try_into()?)
-                .or_else(|_| TileBlock::new_sequence(tiles.try_into()?)),
-            _ => Err(anyhow!("{} is too long to be a block", tiles.len())),
+            2 => TileBlock::new_pair(tiles.try_into().unwrap()),
+            3 => TileBlock::new_triplet(tiles.try_into().unwrap())
+                .or_else(|_| TileBlock::new_sequence(tiles.try_into().unwrap())),
+            4 => TileBlock::new_quad(tiles.try_into().unwrap()),
+            _ => Err(Error::InvalidBlock(tiles.to_vec())),
+        }
+    }
+
+    /// mark a block as called from others
+    pub(crate) fn into_open(self) -> Self {
+        TileBlock {
+            is_open: true,
+            ..self
         }
     }
 
+    /// swap a normal copy of a red five for the red one, `false` if none is here
+    pub(crate) fn paint_red(&mut self, red: Tile) -> bool {
+        let len = self.len() as usize;
+        match self.tiles[..len]
+            .iter()
+            .position(|&tile| tile == red && !tile.is_red())
+        {
+            Some(index) => {
+                self.tiles[index] = red;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn kind(&self) -> BlockKind {
+        match self.block_type {
+            BlockType::Quad => BlockKind::Quad,
+            BlockType::Triplet => BlockKind::Triplet,
+            BlockType::Sequence => BlockKind::Sequence,
+            BlockType::Pair => BlockKind::Pair,
+            BlockType::Orphan => BlockKind::Single,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// a quad is also a triplet
     pub fn triplet(&self) -> Option<Tile> {
-        if let BlockType::Triplet = self.block_type {
+        if let BlockType::Triplet | BlockType::Quad = self.block_type {
+            Some(self.tiles[0])
+        } else {
+            None
+        }
+    }
+
+    pub fn quad(&self) -> Option<Tile> {
+        if let BlockType::Quad = self.block_type {
             Some(self.tiles[0])
         } else {
             None
@@ -106,10 +178,11 @@ impl TileBlock {
         }
     }
 
-    pub fn len(&self) -> u8 {
+    pub(crate) fn len(&self) -> u8 {
         match self.block_type {
+            BlockType::Quad => 4,
             BlockType::Triplet | BlockType::Sequence => 3,
-            BlockType::Pair | BlockType::Incompleted => 2,
+            BlockType::Pair => 2,
             BlockType::Orphan => 1,
         }
     }
@@ -119,9 +192,25 @@ impl TileBlock {
     }
 }
 
+impl Display for TileBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = self
+            .tiles()
+            .iter()
+            .map(|tile| tile.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "[{text}]")
+    }
+}
+
+/// by tiles first, a closed block before the same open one, agreeing with `Eq`
 impl Ord for TileBlock {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.tiles().cmp(other.tiles())
+        self.tiles()
+            .cmp(other.tiles())
+            .then(self.block_type.cmp(&other.block_type))
+            .then(self.is_open.cmp(&other.is_open))
     }
 }
 
@@ -130,3 +219,27 @@ impl PartialOrd for TileBlock {
         Some(self.cmp(other))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn ord() {
+        let closed = TileBlock::new_triplet([T_1P; 3]).unwrap();
+        let open = closed.into_open();
+        assert_ne!(closed, open);
+        assert_eq!(closed.cmp(&open), Ordering::Less);
+        assert_eq!(closed.cmp(&closed), Ordering::Equal);
+
+        let sequence = TileBlock::new_sequence([T_1P, T_2P, T_3P]).unwrap();
+        assert!(closed < sequence);
+        assert!(open < sequence);
+        let mut blocks = vec![open, sequence, closed, open];
+        blocks.sort();
+        blocks.dedup();
+        assert_eq!(blocks, [closed, open, sequence]);
+    }
+}
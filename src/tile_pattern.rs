@@ -1,21 +1,52 @@
-use crate::{tile::Tile, tile_block::TileBlock, yaku::Yaku, T_2S, T_3S, T_4S, T_6S, T_8S, T_HATSU};
+use crate::{
+    context::{Riichi, WinContext, WinMethod},
+    decomposition::Decomposition,
+    fu::{Fu, FuItem},
+    rules::RenhouRule,
+    tile::Tile,
+    tile_block::TileBlock,
+    wait::Wait,
+    yaku::Yaku,
+    T_2S, T_3S, T_4S, T_6S, T_8S, T_HATSU,
+};
 use std::collections::HashMap;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TilePattern {
     /// could be
-    /// [1] * 14            kokushi
-    /// [2] * 7             chiitoi
-    /// [3] * 4 + [2] * 1   common
+    /// [1] * 14                kokushi
+    /// [2] * 7                 chiitoi
+    /// [3 or 4] * 4 + [2] * 1  common
     pub pattern: Vec<TileBlock>,
     pub last_draw: Tile,
+    /// index of the block completed by `last_draw`
+    pub win_block: usize,
+    pub context: WinContext,
 }
 
 impl TilePattern {
-    pub fn new(pattern: Vec<TileBlock>, last_draw: Tile) -> Self {
-        assert_eq!(pattern.iter().map(|block| block.len()).sum::<u8>(), 14);
+    pub fn new(
+        pattern: Vec<TileBlock>,
+        last_draw: Tile,
+        win_block: usize,
+        context: WinContext,
+    ) -> Self {
+        let quad_num = pattern
+            .iter()
+            .filter(|block| block.quad().is_some())
+            .count() as u8;
+        assert_eq!(
+            pattern.iter().map(|block| block.len()).sum::<u8>(),
+            14 + quad_num
+        );
         assert!(pattern.len() == 5 || pattern.len() == 7 || pattern.len() == 14);
-        Self { pattern, last_draw }
+        assert!(pattern[win_block].tiles().contains(&last_draw));
+        Self {
+            pattern,
+            last_draw,
+            win_block,
+            context,
+        }
     }
 
     pub fn yakus(&self) -> Vec<Yaku> {
@@ -61,10 +92,49 @@ impl TilePattern {
             ret.push(Yaku::Chinroutou);
         }
 
+        if self.is_suukantsu() {
+            ret.push(Yaku::Suukantsu);
+        }
+
+        if self.context.situation.first_draw {
+            match self.context.win_method {
+                WinMethod::Tsumo if self.context.is_dealer() => ret.push(Yaku::Tenhou),
+                WinMethod::Tsumo => ret.push(Yaku::Chiihou),
+                WinMethod::Ron if self.is_renhou(RenhouRule::Yakuman) => ret.push(Yaku::Renhou),
+                WinMethod::Ron => {}
+            }
+        }
+
         if !ret.is_empty() {
             return ret;
         }
 
+        match self.context.riichi {
+            Some(Riichi::Riichi) => ret.push(Yaku::Riichi),
+            Some(Riichi::DoubleRiichi) => ret.push(Yaku::DoubleRiichi),
+            None => {}
+        }
+
+        if self.context.riichi.is_some() && self.context.ippatsu && self.context.rules.ippatsu {
+            ret.push(Yaku::Ippatsu);
+        }
+
+        if self.context.situation.haitei {
+            ret.push(Yaku::Haiteiraoyue);
+        }
+
+        if self.context.situation.houtei {
+            ret.push(Yaku::Houteiraoyui);
+        }
+
+        if self.context.situation.rinshan {
+            ret.push(Yaku::Rinshankaihou);
+        }
+
+        if self.context.situation.chankan {
+            ret.push(Yaku::Chankan);
+        }
+
         if self.is_chiniisou() {
             ret.push(Yaku::Chiniisou);
         } else if self.is_honiisou() {
@@ -89,6 +159,18 @@ impl TilePattern {
             ret.push(Yaku::Tanyao)
         }
 
+        if self.is_menzenchin_tsumohou() {
+            ret.push(Yaku::MenzenchinTsumohou)
+        }
+
+        if self.have_triplet(self.context.seat_wind.into()) {
+            ret.push(Yaku::YakuhaiJikaze(self.context.seat_wind));
+        }
+
+        if self.have_triplet(self.context.round_wind.into()) {
+            ret.push(Yaku::YakuhaiBakaze(self.context.round_wind));
+        }
+
         ret.append(
             &mut self
                 .have_yakuhai_sangenpai()
@@ -109,6 +191,10 @@ impl TilePattern {
             ret.push(Yaku::Toitoihou);
         }
 
+        if self.is_sankantsu() {
+            ret.push(Yaku::Sankantsu);
+        }
+
         if self.is_sanankou() {
             ret.push(Yaku::Sanankou);
         }
@@ -132,18 +218,110 @@ impl TilePattern {
         ret
     }
 
-    fn is_tanyao(&self) -> bool {
-        self.pattern.iter().all(|block| {
+    pub fn fu(&self) -> Fu {
+        if self.pattern.len() == 7 {
+            return Fu {
+                items: vec![(FuItem::Chiitoitsu, 25)],
+            };
+        }
+
+        let mut items = vec![(FuItem::Base, 20)];
+        let is_pinfu = self.is_pinfu();
+        match self.context.win_method {
+            WinMethod::Ron if self.is_closed() => items.push((FuItem::MenzenRon, 10)),
+            WinMethod::Tsumo if !is_pinfu => items.push((FuItem::Tsumo, 2)),
+            _ => {}
+        }
+
+        for block in &self.pattern {
             if let Some(tile) = block.triplet() {
-                tile.is_numbered() && !tile.is_terminal()
-            } else if let Some(tile) = block.sequence() {
-                tile.is_numbered() && tile.number() > 1 && tile.number() < 7
+                items.push(FuItem::triplet(
+                    tile,
+                    block.quad().is_some(),
+                    self.is_concealed_triplet(block),
+                ));
             } else if let Some(tile) = block.pair() {
-                tile.is_numbered() && !tile.is_terminal()
-            } else {
-                unreachable!()
+                if tile.is_dragon() {
+                    items.push((FuItem::YakuhaiPair(tile), 2));
+                }
+                let wind = tile.wind();
+                if wind == Some(self.context.seat_wind) && wind == Some(self.context.round_wind) {
+                    let fu = self.context.rules.double_wind_pair_fu.fu();
+                    items.push((FuItem::YakuhaiPair(tile), fu));
+                } else if wind == Some(self.context.seat_wind)
+                    || wind == Some(self.context.round_wind)
+                {
+                    items.push((FuItem::YakuhaiPair(tile), 2));
+                }
             }
-        })
+        }
+
+        if !is_pinfu {
+            let wait = self.wait();
+            if wait.fu() > 0 {
+                items.push((FuItem::Wait(wait), wait.fu()));
+            }
+        }
+
+        if !self.is_closed() && items.iter().map(|(_, fu)| fu).sum::<u8>() == 20 {
+            items.push((FuItem::OpenPinfu, 10));
+        }
+        Fu { items }
+    }
+
+    pub fn decomposition(&self) -> Decomposition {
+        Decomposition::new(self.pattern.clone(), Some((self.win_block, self.last_draw)))
+    }
+
+    /// the wait of the block completed by the last draw
+    pub fn wait(&self) -> Wait {
+        Wait::of(&self.pattern[self.win_block], self.last_draw)
+    }
+
+    /// 人和 scored as `rule`
+    pub(crate) fn is_renhou(&self, rule: RenhouRule) -> bool {
+        self.context.situation.first_draw
+            && self.context.win_method == WinMethod::Ron
+            && !self.context.is_dealer()
+            && self.context.rules.renhou == rule
+    }
+
+    fn is_closed(&self) -> bool {
+        self.pattern.iter().all(|block| !block.is_open())
+    }
+
+    fn is_tanyao(&self) -> bool {
+        (self.context.rules.kuitan || self.is_closed())
+            && self.pattern.iter().all(|block| {
+                if let Some(tile) = block.triplet() {
+                    tile.is_numbered() && !tile.is_terminal()
+                } else if let Some(tile) = block.sequence() {
+                    tile.is_numbered() && tile.number() > 1 && tile.number() < 7
+                } else if let Some(tile) = block.pair() {
+                    tile.is_numbered() && !tile.is_terminal()
+                } else {
+                    unreachable!()
+                }
+            })
+    }
+
+    fn is_menzenchin_tsumohou(&self) -> bool {
+        self.is_closed() && self.context.win_method == WinMethod::Tsumo
+    }
+
+    fn have_triplet(&self, tile: Tile) -> bool {
+        self.pattern.len() == 5
+            && self
+                .pattern
+                .iter()
+                .any(|block| block.triplet() == Some(tile))
+    }
+
+    /// dragons, seat wind and round wind
+    fn is_yakuhai(&self, tile: Tile) -> bool {
+        tile.is_dragon()
+            || tile.wind() == Some(self.context.seat_wind)
+            || tile.wind() == Some(self.context.round_wind)
     }
 
     fn have_yakuhai_sangenpai(&self) -> Vec<Tile> {
@@ -163,32 +341,23 @@ impl TilePattern {
     }
 
     fn is_pinfu(&self) -> bool {
-        if self.pattern.len() == 5 {
-            let mut is_sidewait = false;
-            for block in &self.pattern {
-                if let Some(tile) = block.sequence() {
-                    if tile.tile_type() == self.last_draw.tile_type()
-                        && (tile.number() == self.last_draw.number()
-                            || tile.number() + 2 == self.last_draw.number())
-                    {
-                        is_sidewait = true;
-                    }
+        self.pattern.len() == 5
+            && self.is_closed()
+            && (self.context.rules.pinfu_tsumo || self.context.win_method == WinMethod::Ron)
+            && self.wait() == Wait::Ryanmen
+            && self.pattern.iter().all(|block| {
+                if block.sequence().is_some() {
+                    true
                 } else if let Some(tile) = block.pair() {
-                    if tile.is_dragon() {
-                        return false;
-                    }
+                    !self.is_yakuhai(tile)
                 } else {
-                    return false;
+                    false
                 }
-            }
-            is_sidewait
-        } else {
-            false
-        }
+            })
     }
 
     fn is_iipeikou(&self) -> bool {
-        if self.pattern.len() == 5 {
+        if self.pattern.len() == 5 && self.is_closed() {
             let mut seq_starts = Vec::with_capacity(4);
             for block in &self.pattern {
                 if let Some(tile) = block.sequence() {
@@ -214,12 +383,12 @@ impl TilePattern {
                 target.iter().all(|tile| tile.is_numbered())
                     && target[0].number() == target[1].number()
                     && target[0].number() == target[2].number()
-                    && target[0].tile_type() != target[1].tile_type()
-                    && target[0].tile_type() != target[2].tile_type()
-                    && target[1].tile_type() != target[2].tile_type()
+                    && target[0].suit() != target[1].suit()
+                    && target[0].suit() != target[2].suit()
+                    && target[1].suit() != target[2].suit()
             };
             match triplets.len() {
-                0 | 1 | 2 => false,
+                0..=2 => false,
                 3 => check_three_triplet(&triplets),
                 4 => (0..4).any(|index| {
                     let mut three_triplets = triplets.clone();
@@ -243,14 +412,31 @@ impl TilePattern {
                 .all(|block| block.triplet().is_some() || block.pair().is_some())
     }
 
+    fn is_sankantsu(&self) -> bool {
+        self.pattern
+            .iter()
+            .filter(|block| block.quad().is_some())
+            .count()
+            == 3
+    }
+
+    /// a triplet completed by ron is not concealed
+    fn is_concealed_triplet(&self, block: &TileBlock) -> bool {
+        block.triplet().is_some()
+            && !block.is_open()
+            && !(self.context.win_method == WinMethod::Ron
+                && *block == self.pattern[self.win_block])
+    }
+
+    fn concealed_triplet_num(&self) -> usize {
+        self.pattern
+            .iter()
+            .filter(|block| self.is_concealed_triplet(block))
+            .count()
+    }
+
     fn is_sanankou(&self) -> bool {
-        self.pattern.len() == 5
-            && self
-                .pattern
-                .iter()
-                .filter_map(|block| block.triplet())
-                .count()
-                >= 3
+        self.pattern.len() == 5 && self.concealed_triplet_num() >= 3
     }
 
     fn is_shousangen(&self) -> bool {
@@ -301,11 +487,11 @@ impl TilePattern {
                 target.iter().all(|tile| tile.is_numbered())
                     && target[0].number() + 3 == target[1].number()
                     && target[0].number() + 6 == target[2].number()
-                    && target[0].tile_type() == target[1].tile_type()
-                    && target[0].tile_type() == target[2].tile_type()
+                    && target[0].suit() == target[1].suit()
+                    && target[0].suit() == target[2].suit()
             };
             match seq_starts.len() {
-                0 | 1 | 2 => false,
+                0..=2 => false,
                 3 => check_three_sequence(&seq_starts),
                 4 => (0..4).any(|index| {
                     let mut three_seq_starts = seq_starts.clone();
@@ -332,12 +518,12 @@ impl TilePattern {
                 target.iter().all(|tile| tile.is_numbered())
                     && target[0].number() == target[1].number()
                     && target[0].number() == target[2].number()
-                    && target[0].tile_type() != target[1].tile_type()
-                    && target[0].tile_type() != target[2].tile_type()
-                    && target[1].tile_type() != target[2].tile_type()
+                    && target[0].suit() != target[1].suit()
+                    && target[0].suit() != target[2].suit()
+                    && target[1].suit() != target[2].suit()
             };
             match seq_starts.len() {
-                0 | 1 | 2 => false,
+                0..=2 => false,
                 3 => check_three_sequence(&seq_starts),
                 4 => (0..4).any(|index| {
                     let mut three_seq_starts = seq_starts.clone();
@@ -354,7 +540,7 @@ impl TilePattern {
     }
 
     fn is_ryanpeikou(&self) -> bool {
-        if self.pattern.len() == 5 {
+        if self.pattern.len() == 5 && self.is_closed() {
             let mut seq_starts = Vec::with_capacity(4);
             for block in &self.pattern {
                 if let Some(tile) = block.sequence() {
@@ -392,7 +578,7 @@ impl TilePattern {
             .collect::<Vec<_>>();
         numbered_tiles
             .iter()
-            .all(|tile| tile.tile_type() == numbered_tiles[0].tile_type())
+            .all(|tile| tile.suit() == numbered_tiles[0].suit())
     }
 
     fn is_chiniisou(&self) -> bool {
@@ -420,11 +606,7 @@ impl TilePattern {
     }
 
     fn is_suuankou(&self) -> bool {
-        self.pattern.len() == 5
-            && self
-                .pattern
-                .iter()
-                .all(|block| block.triplet().is_some() || block.pair().is_some())
+        self.pattern.len() == 5 && self.is_closed() && self.concealed_triplet_num() == 4
     }
 
     fn is_tsuuiisou(&self) -> bool {
@@ -456,6 +638,14 @@ impl TilePattern {
             })
     }
 
+    fn is_suukantsu(&self) -> bool {
+        self.pattern
+            .iter()
+            .filter(|block| block.quad().is_some())
+            .count()
+            == 4
+    }
+
     fn is_kokushimusou(&self) -> bool {
         self.pattern.len() == 14
     }
@@ -474,15 +664,16 @@ impl TilePattern {
     }
 
     fn is_chuurenpoutou(&self) -> bool {
-        if self.pattern.len() == 5 {
+        if self.pattern.len() == 5 && self.is_closed() {
             let all_tiles = self
                 .pattern
                 .iter()
                 .flat_map(|block| block.tiles())
                 .collect::<Vec<_>>();
-            if all_tiles
-                .iter()
-                .any(|tile| tile.tile_type() != all_tiles[0].tile_type())
+            if all_tiles.len() != 14
+                || all_tiles
+                    .iter()
+                    .any(|tile| tile.tile_type() != all_tiles[0].tile_type())
             {
                 return false;
             }
@@ -491,7 +682,7 @@ impl TilePattern {
                 .iter()
                 .for_each(|tile| *map.entry(tile.number()).or_default() += 1);
             (1..=9).all(|num| {
-                map.get(&num).is_some()
+                map.contains_key(&num)
                     && if num == 1 || num == 9 {
                         *map.get(&num).unwrap() >= 3
                     } else {
@@ -504,14 +695,7 @@ impl TilePattern {
     }
 
     fn is_suuankoutanki(&self) -> bool {
-        self.is_suuankou()
-            && self.pattern.iter().all(|block| {
-                if let Some(tile) = block.pair() {
-                    tile == self.last_draw
-                } else {
-                    true
-                }
-            })
+        self.is_suuankou() && self.wait() == Wait::Tanki
     }
 
     fn is_kokushimusou13(&self) -> bool {
@@ -553,7 +737,9 @@ impl TilePattern {
 
 #[cfg(test)]
 mod tests {
-    use crate::tile_block::TileBlock;
+    use crate::{
+        rules::DoubleWindPairFu, tile::Wind, tile_block::TileBlock, T_1M, T_2P, T_9S, T_CHUN, T_TON,
+    };
 
     use super::*;
 
@@ -566,9 +752,103 @@ mod tests {
                 )
                 .unwrap()
             })
-            .collect();
+            .collect::<Vec<TileBlock>>();
         let last_draw = last_draw.parse().unwrap();
-        TilePattern { pattern, last_draw }
+        // most yaku tests don't care which block the last draw completed
+        let win_block = pattern
+            .iter()
+            .position(|block| block.tiles().contains(&last_draw))
+            .unwrap_or(0);
+        TilePattern {
+            pattern,
+            last_draw,
+            win_block,
+            context: WinContext::default(),
+        }
+    }
+
+    #[test]
+    fn fu() {
+        let tileset = vec![
+            vec!["2p", "3p", "4p"],
+            vec!["5m", "6m", "7m"],
+            vec!["3s", "4s", "5s"],
+            vec!["7s", "8s", "9s"],
+            vec!["3p", "3p"],
+        ];
+        let mut pattern = build_pattern(tileset, "2p");
+        assert_eq!(pattern.fu().total(), 20);
+        pattern.context.win_method = WinMethod::Ron;
+        assert_eq!(pattern.fu().total(), 30);
+        pattern.pattern[3] = pattern.pattern[3].into_open();
+        assert_eq!(
+            pattern.fu().items,
+            vec![(FuItem::Base, 20), (FuItem::OpenPinfu, 10)]
+        );
+
+        let tileset = vec![
+            vec!["1p", "1p", "1p"],
+            vec!["2m", "3m", "4m"],
+            vec!["4s", "5s", "6s"],
+            vec!["6p", "7p", "8p"],
+            vec!["chun", "chun"],
+        ];
+        let mut pattern = build_pattern(tileset, "3m");
+        pattern.context.win_method = WinMethod::Ron;
+        assert_eq!(
+            pattern.fu().items,
+            vec![
+                (FuItem::Base, 20),
+                (FuItem::MenzenRon, 10),
+                (
+                    FuItem::Triplet {
+                        tile: "1p".parse().unwrap(),
+                        is_quad: false,
+                        is_concealed: true
+                    },
+                    8
+                ),
+                (FuItem::YakuhaiPair(T_CHUN), 2),
+                (FuItem::Wait(Wait::Kanchan), 2),
+            ]
+        );
+        assert_eq!(pattern.fu().total(), 50);
+
+        let tileset = vec![
+            vec!["1p", "1p", "1p", "1p"],
+            vec!["2m", "3m", "4m"],
+            vec!["5s", "5s", "5s", "5s"],
+            vec!["7p", "8p", "9p"],
+            vec!["ton", "ton"],
+        ];
+        let mut pattern = build_pattern(tileset, "7p");
+        pattern.context = pattern.context.with_winds(Wind::Ton, Wind::Ton);
+        pattern.pattern[2] = pattern.pattern[2].into_open();
+        assert_eq!(
+            pattern
+                .fu()
+                .items
+                .iter()
+                .map(|(_, fu)| fu)
+                .collect::<Vec<_>>(),
+            vec![&20, &2, &32, &8, &4, &2]
+        );
+        assert_eq!(pattern.fu().total(), 70);
+        pattern.context.rules.double_wind_pair_fu = DoubleWindPairFu::Two;
+        assert_eq!(pattern.fu().items[4], (FuItem::YakuhaiPair(T_TON), 2));
+        assert_eq!(pattern.fu().total(), 70);
+
+        let tileset = vec![
+            vec!["1p", "1p"],
+            vec!["2p", "2p"],
+            vec!["3m", "3m"],
+            vec!["4p", "4p"],
+            vec!["5s", "5s"],
+            vec!["6m", "6m"],
+            vec!["7p", "7p"],
+        ];
+        let pattern = build_pattern(tileset, "7p");
+        assert_eq!(pattern.fu().total(), 25);
     }
 
     #[test]
@@ -617,6 +897,23 @@ mod tests {
         assert!(pattern.have_yakuhai_sangenpai().is_empty());
     }
 
+    #[test]
+    fn kazehai() {
+        let tileset = vec![
+            vec!["2p", "2p", "2p"],
+            vec!["ton", "ton", "ton"],
+            vec!["nan", "nan", "nan"],
+            vec!["6s", "6s", "6s"],
+            vec!["3p", "3p"],
+        ];
+        let mut pattern = build_pattern(tileset, "3p");
+        assert!(pattern.have_triplet(pattern.context.seat_wind.into()));
+        assert!(pattern.have_triplet(pattern.context.round_wind.into()));
+        pattern.context = pattern.context.with_winds(Wind::Nan, Wind::Shaa);
+        assert!(!pattern.have_triplet(pattern.context.seat_wind.into()));
+        assert!(pattern.have_triplet(pattern.context.round_wind.into()));
+    }
+
     #[test]
     fn pinfu() {
         let tileset = vec![
@@ -652,6 +949,32 @@ mod tests {
         ];
         let pattern = build_pattern(tileset, "9s");
         assert!(!pattern.is_pinfu());
+
+        let tileset = vec![
+            vec!["2p", "3p", "4p"],
+            vec!["5m", "6m", "7m"],
+            vec!["3s", "4s", "5s"],
+            vec!["7s", "8s", "9s"],
+            vec!["3p", "3p"],
+        ];
+        let mut pattern = build_pattern(tileset, "2p");
+        pattern.pattern[3] = pattern.pattern[3].into_open();
+        assert!(!pattern.is_pinfu());
+
+        let tileset = vec![
+            vec!["2p", "3p", "4p"],
+            vec!["5m", "6m", "7m"],
+            vec!["3s", "4s", "5s"],
+            vec!["7s", "8s", "9s"],
+            vec!["nan", "nan"],
+        ];
+        let mut pattern = build_pattern(tileset, "2p");
+        pattern.context = pattern.context.with_winds(Wind::Ton, Wind::Ton);
+        assert!(pattern.is_pinfu());
+        pattern.context = pattern.context.with_winds(Wind::Ton, Wind::Nan);
+        assert!(!pattern.is_pinfu());
+        pattern.context = pattern.context.with_winds(Wind::Nan, Wind::Pei);
+        assert!(!pattern.is_pinfu());
     }
 
     #[test]
@@ -752,8 +1075,10 @@ mod tests {
             vec!["2m", "3m", "4m"],
             vec!["3p", "3p"],
         ];
-        let pattern = build_pattern(tileset.clone(), "2p");
+        let mut pattern = build_pattern(tileset.clone(), "2p");
         assert!(pattern.is_sanankou());
+        pattern.pattern[0] = pattern.pattern[0].into_open();
+        assert!(!pattern.is_sanankou());
 
         let tileset = vec![
             vec!["2p", "2p", "2p"],
@@ -776,6 +1101,33 @@ mod tests {
         assert!(!pattern.is_sanankou());
     }
 
+    #[test]
+    fn sankantsu() {
+        let tileset = vec![
+            vec!["2p", "2p", "2p", "2p"],
+            vec!["3s", "3s", "3s", "3s"],
+            vec!["2s", "2s", "2s", "2s"],
+            vec!["2m", "3m", "4m"],
+            vec!["3p", "3p"],
+        ];
+        let mut pattern = build_pattern(tileset.clone(), "3p");
+        assert!(pattern.is_sankantsu());
+        assert!(pattern.is_sanankou());
+        pattern.pattern[0] = pattern.pattern[0].into_open();
+        assert!(pattern.is_sankantsu());
+        assert!(!pattern.is_sanankou());
+
+        let tileset = vec![
+            vec!["2p", "2p", "2p", "2p"],
+            vec!["3s", "3s", "3s", "3s"],
+            vec!["2s", "2s", "2s"],
+            vec!["2m", "3m", "4m"],
+            vec!["3p", "3p"],
+        ];
+        let pattern = build_pattern(tileset.clone(), "3p");
+        assert!(!pattern.is_sankantsu());
+    }
+
     #[test]
     fn shousangen() {
         let tileset = vec![
@@ -1090,8 +1442,11 @@ mod tests {
             vec!["1p", "1p", "1p"],
             vec!["2p", "2p"],
         ];
-        let pattern = build_pattern(tileset, "haku");
+        let mut pattern = build_pattern(tileset, "haku");
         assert!(pattern.is_suuankou());
+        pattern.context.win_method = WinMethod::Ron;
+        assert!(!pattern.is_suuankou());
+        assert!(pattern.is_sanankou());
 
         let tileset = vec![
             vec!["haku", "haku", "haku"],
@@ -1102,6 +1457,78 @@ mod tests {
         ];
         let pattern = build_pattern(tileset, "haku");
         assert!(!pattern.is_suuankou());
+
+        let tileset = vec![
+            vec!["haku", "haku", "haku"],
+            vec!["hatsu", "hatsu", "hatsu"],
+            vec!["3p", "3p", "3p"],
+            vec!["1p", "2p", "3p"],
+            vec!["5s", "5s"],
+        ];
+        let mut pattern = build_pattern(tileset, "3p");
+        pattern.context.win_method = WinMethod::Ron;
+        assert!(!pattern.is_sanankou());
+        pattern.win_block = 3;
+        assert!(pattern.is_sanankou());
+    }
+
+    #[test]
+    fn wait() {
+        let tileset = vec![
+            vec!["1m", "2m", "3m"],
+            vec!["4p", "5p", "6p"],
+            vec!["7s", "8s", "9s"],
+            vec!["3s", "4s", "5s"],
+            vec!["2p", "2p"],
+        ];
+        let mut pattern = build_pattern(tileset.clone(), "7s");
+        assert_eq!(pattern.wait(), Wait::Penchan);
+        assert!(!pattern.is_pinfu());
+        assert!(pattern
+            .fu()
+            .items
+            .contains(&(FuItem::Wait(Wait::Penchan), 2)));
+        pattern.last_draw = T_9S;
+        assert!(pattern.is_pinfu());
+
+        let mut pattern = build_pattern(tileset, "3m");
+        assert_eq!(pattern.wait(), Wait::Penchan);
+        assert!(!pattern.is_pinfu());
+        pattern.last_draw = T_1M;
+        assert_eq!(pattern.wait(), Wait::Ryanmen);
+        assert!(pattern.is_pinfu());
+
+        let tileset = vec![
+            vec!["haku", "haku", "haku"],
+            vec!["hatsu", "hatsu", "hatsu"],
+            vec!["chun", "chun", "chun"],
+            vec!["1p", "1p", "1p"],
+            vec!["2p", "2p"],
+        ];
+        let mut pattern = build_pattern(tileset, "1p");
+        assert_eq!(pattern.wait(), Wait::Shanpon);
+        assert!(!pattern.is_suuankoutanki());
+        pattern.last_draw = T_2P;
+        pattern.win_block = 4;
+        assert!(pattern.is_suuankoutanki());
+    }
+
+    #[test]
+    fn menzenchin_tsumohou() {
+        let tileset = vec![
+            vec!["2p", "3p", "4p"],
+            vec!["5m", "6m", "7m"],
+            vec!["3s", "4s", "5s"],
+            vec!["7s", "8s", "9s"],
+            vec!["3p", "3p"],
+        ];
+        let mut pattern = build_pattern(tileset, "2p");
+        assert!(pattern.is_menzenchin_tsumohou());
+        pattern.context.win_method = WinMethod::Ron;
+        assert!(!pattern.is_menzenchin_tsumohou());
+        pattern.context.win_method = WinMethod::Tsumo;
+        pattern.pattern[3] = pattern.pattern[3].into_open();
+        assert!(!pattern.is_menzenchin_tsumohou());
     }
 
     #[test]
@@ -1193,6 +1620,30 @@ mod tests {
         assert!(!pattern.is_chinroutou());
     }
 
+    #[test]
+    fn suukantsu() {
+        let tileset = vec![
+            vec!["2p", "2p", "2p", "2p"],
+            vec!["3s", "3s", "3s", "3s"],
+            vec!["2s", "2s", "2s", "2s"],
+            vec!["haku", "haku", "haku", "haku"],
+            vec!["3p", "3p"],
+        ];
+        let pattern = build_pattern(tileset.clone(), "3p");
+        assert!(pattern.is_suukantsu());
+        assert!(pattern.is_suuankoutanki());
+
+        let tileset = vec![
+            vec!["2p", "2p", "2p", "2p"],
+            vec!["3s", "3s", "3s", "3s"],
+            vec!["2s", "2s", "2s", "2s"],
+            vec!["haku", "haku", "haku"],
+            vec!["3p", "3p"],
+        ];
+        let pattern = build_pattern(tileset.clone(), "3p");
+        assert!(!pattern.is_suukantsu());
+    }
+
     #[test]
     fn kokushimusou() {
         let tileset = [
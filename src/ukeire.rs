@@ -0,0 +1,73 @@
+use crate::{shanten::shanten, tile::Tile, ALL_TILES};
+
+/// 受入, tiles that lower the shanten and how many copies are still unseen
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ukeire {
+    /// each effective tile with its unseen copies, in `ALL_TILES` order
+    pub tiles: Vec<(Tile, u8)>,
+    /// unseen copies of all effective tiles
+    pub total: u8,
+}
+
+impl Ukeire {
+    /// an effective tile may have no copy left
+    pub fn kinds(&self) -> usize {
+        self.tiles.len()
+    }
+}
+
+/// acceptance of 3n + 1 tiles, `visible` counts every tile seen by the player,
+/// including the hand itself, its melds, all rivers and the dora indicators
+pub fn ukeire(counts: &[u8; 34], meld_num: u8, visible: &[u8; 34]) -> Ukeire {
+    let current = shanten(counts, meld_num);
+    let mut counts = *counts;
+    let mut result = Ukeire::default();
+    for (index, &tile) in ALL_TILES.iter().enumerate() {
+        if counts[index] >= 4 {
+            continue;
+        }
+        counts[index] += 1;
+        if shanten(&counts, meld_num) < current {
+            let unseen = 4u8.saturating_sub(visible[index]);
+            result.tiles.push((tile, unseen));
+            result.total += unseen;
+        }
+        counts[index] -= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn unseen_copies() {
+        // waiting on 3p and 6p, one 3p is in the hand
+        let tileset = ReadyTileSet::from_str("123p 456m 789s 1s2 45p").unwrap();
+        let counts = tile_counts(tileset.concealed());
+        let result = ukeire(&counts, 0, &counts);
+        assert_eq!(result.tiles, vec![(T_3P, 3), (T_6P, 4)]);
+        assert_eq!(result.total, 7);
+
+        let mut visible = counts;
+        visible[T_3P.index()] += 2;
+        visible[T_6P.index()] += 4;
+        let result = ukeire(&counts, 0, &visible);
+        assert_eq!(result.kinds(), 2);
+        assert_eq!(result.tiles, vec![(T_3P, 1), (T_6P, 0)]);
+        assert_eq!(result.total, 1);
+    }
+
+    #[test]
+    fn tenpai() {
+        let tileset = ReadyTileSet::from_str("1112345678999p").unwrap();
+        let counts = tile_counts(tileset.concealed());
+        let result = ukeire(&counts, 0, &counts);
+        assert_eq!(result.kinds(), 9);
+        // three 1p and three 9p are in the hand
+        assert_eq!(result.total, 4 * 9 - 3 - 3 - 7);
+    }
+}
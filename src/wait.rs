@@ -0,0 +1,71 @@
+use crate::{tile::Tile, tile_block::TileBlock};
+
+/// the shape the winning tile completed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Wait {
+    /// 两面
+    Ryanmen,
+    /// 嵌张
+    Kanchan,
+    /// 边张
+    Penchan,
+    /// 双碰
+    Shanpon,
+    /// 单骑, including each wait of kokushi
+    Tanki,
+}
+
+impl Wait {
+    /// `block` must contain `tile`
+    pub(crate) fn of(block: &TileBlock, tile: Tile) -> Self {
+        if block.triplet().is_some() {
+            Wait::Shanpon
+        } else if let Some(start) = block.sequence() {
+            if start.next() == Some(tile) {
+                Wait::Kanchan
+            } else if (start.number() == 1 && tile.number() == 3)
+                || (start.number() == 7 && tile.number() == 7)
+            {
+                Wait::Penchan
+            } else {
+                Wait::Ryanmen
+            }
+        } else {
+            Wait::Tanki
+        }
+    }
+
+    /// kanchan, penchan and tanki give 2 fu
+    pub fn fu(&self) -> u8 {
+        match self {
+            Wait::Kanchan | Wait::Penchan | Wait::Tanki => 2,
+            Wait::Ryanmen | Wait::Shanpon => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn wait_of() {
+        let seq = |tiles| TileBlock::new_sequence(tiles).unwrap();
+        assert_eq!(Wait::of(&seq([T_2P, T_3P, T_4P]), T_2P), Wait::Ryanmen);
+        assert_eq!(Wait::of(&seq([T_2P, T_3P, T_4P]), T_4P), Wait::Ryanmen);
+        assert_eq!(Wait::of(&seq([T_2P, T_3P, T_4P]), T_3P), Wait::Kanchan);
+        assert_eq!(Wait::of(&seq([T_1S, T_2S, T_3S]), T_3S), Wait::Penchan);
+        assert_eq!(Wait::of(&seq([T_1S, T_2S, T_3S]), T_1S), Wait::Ryanmen);
+        assert_eq!(Wait::of(&seq([T_7M, T_8M, T_9M]), T_7M), Wait::Penchan);
+        assert_eq!(Wait::of(&seq([T_7M, T_8M, T_9M]), T_9M), Wait::Ryanmen);
+        assert_eq!(
+            Wait::of(&TileBlock::new_triplet([T_HAKU; 3]).unwrap(), T_HAKU),
+            Wait::Shanpon
+        );
+        assert_eq!(
+            Wait::of(&TileBlock::new_unknown(&[T_5M, T_0M]).unwrap(), T_0M),
+            Wait::Tanki
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{
+    decomposition::Decomposition,
+    dora::Dora,
+    fu::Fu,
+    score::Points,
+    wait::Wait,
+    yaku::{Han, Yaku},
+};
+
+/// evaluation of a completed hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WinResult {
+    /// 和了
+    Win(Win),
+    /// 无役, complete shape without any yaku
+    NoYaku {
+        decomposition: Decomposition,
+        wait: Wait,
+    },
+}
+
+/// everything about a valid win
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Win {
+    pub decomposition: Decomposition,
+    pub wait: Wait,
+    /// each yaku with its han, already reduced for an open hand
+    pub yakus: Vec<(Yaku, Han)>,
+    pub dora: Dora,
+    /// yaku and dora together
+    pub han: Han,
+    pub fu: Fu,
+    /// limit and payouts
+    pub points: Points,
+}
+
+impl WinResult {
+    pub fn is_win(&self) -> bool {
+        matches!(self, WinResult::Win(_))
+    }
+
+    pub fn decomposition(&self) -> &Decomposition {
+        match self {
+            WinResult::Win(win) => &win.decomposition,
+            WinResult::NoYaku { decomposition, .. } => decomposition,
+        }
+    }
+}
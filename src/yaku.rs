@@ -1,4 +1,8 @@
-use crate::tile::Tile;
+use crate::{
+    rules::{RenhouRule, Rules},
+    score::Limit,
+    tile::{Tile, Wind},
+};
 use std::{
     fmt::{Debug, Display},
     iter::Sum,
@@ -7,42 +11,42 @@ use std::{
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Yaku {
-    // /// 立直（门前清限定）
-    // Riichi,
+    /// 立直（门前清限定）
+    Riichi,
     /// 断幺九
     Tanyao,
-    // /// 门前清自摸和（门前清限定）
-    // MenzenchinTsumohou,
-    // /// 自风牌
-    // YakuhaiJikaze(Kazehai),
-    // /// 场风牌
-    // YakuhaiBakaze(Kazehai),
+    /// 门前清自摸和（门前清限定）
+    MenzenchinTsumohou,
+    /// 自风牌
+    YakuhaiJikaze(Wind),
+    /// 场风牌
+    YakuhaiBakaze(Wind),
     /// 三元牌
     YakuhaiSangenpai(Tile),
-    /// 平和（门前清限定）(不考虑自风场风，都算平胡)
+    /// 平和（门前清限定）
     Pinfu,
     /// 一杯口（门前清限定）
     Iipeikou,
-    // /// 抢杠
-    // Chankan,
-    // /// 岭上开花
-    // Rinshankaihou,
-    // /// 海底捞月
-    // Haiteiraoyue,
-    // /// 河底捞鱼
-    // Houteiraoyui,
-    // /// 一发
-    // Ippatsu,
+    /// 抢杠
+    Chankan,
+    /// 岭上开花
+    Rinshankaihou,
+    /// 海底捞月
+    Haiteiraoyue,
+    /// 河底捞鱼
+    Houteiraoyui,
+    /// 一发
+    Ippatsu,
     // /// 宝牌（不是役）
     // Dora,
     // /// 赤宝牌（不是役）
     // Akadora,
-    // /// 两立直（门前清限定）
-    // DoubleRiichi,
+    /// 两立直（门前清限定）
+    DoubleRiichi,
     /// 三色同刻
     Sanshokudoukou,
-    // /// 三杠子
-    // Sankantsu,
+    /// 三杠子
+    Sankantsu,
     /// 对对和
     Toitoihou,
     /// 三暗刻
@@ -69,10 +73,12 @@ pub enum Yaku {
     Chiniisou,
     // /// 流局满贯
     // Nagashimangan,
-    // /// 天和（庄家限定）
-    // Tenhou,
-    // /// 地和（子家限定）
-    // Chiihou,
+    /// 天和（庄家限定）
+    Tenhou,
+    /// 地和（子家限定）
+    Chiihou,
+    /// 人和（子家限定）
+    Renhou,
     /// 大三元
     Daisangen,
     /// 四暗刻（门前清限定）
@@ -87,8 +93,8 @@ pub enum Yaku {
     Kokushimusou,
     /// 小四喜
     Shousuushii,
-    // /// 四杠子
-    // Suukantsu,
+    /// 四杠子
+    Suukantsu,
     /// 九莲宝灯（门前清限定）
     Chuurenpoutou,
     /// 四暗刻单骑（门前清限定）
@@ -107,7 +113,8 @@ impl Display for Yaku {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// 番, or the multiple of yakuman
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Han {
     is_yakuman: bool,
     score: u8,
@@ -134,6 +141,15 @@ impl Han {
             score: 2,
         }
     }
+
+    pub fn is_yakuman(&self) -> bool {
+        self.is_yakuman
+    }
+
+    /// han, or the multiple of yakuman
+    pub fn value(&self) -> u8 {
+        self.score
+    }
 }
 
 impl Ord for Han {
@@ -183,10 +199,23 @@ impl Sum for Han {
     }
 }
 
-impl From<Yaku> for Han {
-    fn from(yaku: Yaku) -> Self {
-        match yaku {
-            Yaku::Tanyao | Yaku::YakuhaiSangenpai(_) | Yaku::Pinfu | Yaku::Iipeikou => Han::new(1),
+impl Yaku {
+    /// han of this yaku, some of them are reduced by 1 when the hand is open
+    pub(crate) fn han(&self, is_closed: bool, rules: &Rules) -> Han {
+        let han = match self {
+            Yaku::Riichi
+            | Yaku::Ippatsu
+            | Yaku::Tanyao
+            | Yaku::MenzenchinTsumohou
+            | Yaku::Chankan
+            | Yaku::Rinshankaihou
+            | Yaku::Haiteiraoyue
+            | Yaku::Houteiraoyui
+            | Yaku::YakuhaiJikaze(_)
+            | Yaku::YakuhaiBakaze(_)
+            | Yaku::YakuhaiSangenpai(_)
+            | Yaku::Pinfu
+            | Yaku::Iipeikou => Han::new(1),
             Yaku::Sanshokudoukou
             | Yaku::Toitoihou
             | Yaku::Sanankou
@@ -195,7 +224,9 @@ impl From<Yaku> for Han {
             | Yaku::Chiitoitsu
             | Yaku::Honchantaiyaochuu
             | Yaku::Ikkitsuukan
-            | Yaku::Sanshokudoujun => Han::new(2),
+            | Yaku::Sanshokudoujun
+            | Yaku::Sankantsu
+            | Yaku::DoubleRiichi => Han::new(2),
             Yaku::Ryanpeikou | Yaku::Junchantaiyaochuu | Yaku::Honiisou => Han::new(3),
             Yaku::Chiniisou => Han::new(6),
             Yaku::Daisangen
@@ -205,13 +236,55 @@ impl From<Yaku> for Han {
             | Yaku::Chinroutou
             | Yaku::Kokushimusou
             | Yaku::Shousuushii
-            | Yaku::Chuurenpoutou => Han::yakuman(),
+            | Yaku::Suukantsu
+            | Yaku::Chuurenpoutou
+            | Yaku::Tenhou
+            | Yaku::Chiihou => Han::yakuman(),
+            Yaku::Renhou => match rules.renhou {
+                RenhouRule::Yakuman => Han::yakuman(),
+                // only shown, the points come from `limit`
+                RenhouRule::Mangan => Han::new(5),
+                // not a yaku, so worth nothing
+                RenhouRule::Disabled => Han::new(0),
+            },
             Yaku::Suuankoutanki
             | Yaku::Kokushimusou13
             | Yaku::Junseichuurenpoutou
-            | Yaku::Daisuushii => Han::double_yakuman(),
+            | Yaku::Daisuushii => {
+                if rules.double_yakuman {
+                    Han::double_yakuman()
+                } else {
+                    Han::yakuman()
+                }
+            }
+        };
+        if !is_closed && self.is_reduced_when_open() {
+            Han::new(han.score - 1)
+        } else {
+            han
+        }
+    }
+
+    /// a yaku that sets the points by itself, no other yaku or dora count with it
+    pub(crate) fn limit(&self, rules: &Rules) -> Option<Limit> {
+        match self {
+            Yaku::Renhou if rules.renhou == RenhouRule::Mangan => Some(Limit::Mangan),
+            _ => None,
         }
     }
+
+    /// 副露减1番
+    fn is_reduced_when_open(&self) -> bool {
+        matches!(
+            self,
+            Yaku::Honchantaiyaochuu
+                | Yaku::Ikkitsuukan
+                | Yaku::Sanshokudoujun
+                | Yaku::Junchantaiyaochuu
+                | Yaku::Honiisou
+                | Yaku::Chiniisou
+        )
+    }
 }
 
 #[cfg(test)]
@@ -235,4 +308,30 @@ mod tests {
         assert!(Han::new(10) + Han::new(10) == Han::new(13));
         assert!(Han::new(1) + Han::new(5) == Han::new(6));
     }
+
+    #[test]
+    fn open_han() {
+        let rules = Rules::default();
+        assert_eq!(Yaku::Chiniisou.han(true, &rules), Han::new(6));
+        assert_eq!(Yaku::Chiniisou.han(false, &rules), Han::new(5));
+        assert_eq!(Yaku::Ikkitsuukan.han(false, &rules), Han::new(1));
+        assert_eq!(Yaku::Toitoihou.han(false, &rules), Han::new(2));
+        assert_eq!(Yaku::Daisangen.han(false, &rules), Han::yakuman());
+    }
+
+    #[test]
+    fn rules_han() {
+        let mut rules = Rules::default();
+        assert_eq!(Yaku::Suuankoutanki.han(true, &rules), Han::double_yakuman());
+        rules.double_yakuman = false;
+        assert_eq!(Yaku::Suuankoutanki.han(true, &rules), Han::yakuman());
+        assert_eq!(Yaku::Daisuushii.han(false, &rules), Han::yakuman());
+        rules.renhou = RenhouRule::Disabled;
+        assert_eq!(Yaku::Renhou.han(true, &rules), Han::new(0));
+        rules.renhou = RenhouRule::Mangan;
+        assert_eq!(Yaku::Renhou.limit(&rules), Some(Limit::Mangan));
+        rules.renhou = RenhouRule::Yakuman;
+        assert_eq!(Yaku::Renhou.han(true, &rules), Han::yakuman());
+        assert_eq!(Yaku::Renhou.limit(&rules), None);
+    }
 }